@@ -0,0 +1,82 @@
+use day4::search::{search, Goal, SearchOutcome};
+use day4::BingoGame;
+use std::env;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::process;
+
+/// Finds a draw order that makes a chosen board win first or last.
+///
+/// Usage: adversary <first|last> <board number> [--node-limit N] [input file]
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+    let goal = match args.next().as_deref() {
+        Some("first") => Goal::WinFirst,
+        Some("last") => Goal::WinLast,
+        _ => {
+            eprintln!("Usage: adversary <first|last> <board number> [--node-limit N] [input file]");
+            process::exit(2);
+        }
+    };
+    let board_number = args.next().unwrap().parse::<usize>().unwrap();
+    let mut node_limit = 500_000;
+    let mut path = "./input.txt".to_string();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--node-limit" => node_limit = args.next().unwrap().parse::<usize>().unwrap(),
+            _ => path = arg,
+        }
+    }
+
+    let file = fs::File::open(&path)?;
+    let mut reader = io::BufReader::new(file);
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+
+    let game = BingoGame::from_input(&input).unwrap();
+    match search(game.boards(), game.draws(), board_number, goal, node_limit).unwrap() {
+        SearchOutcome::Found {
+            prefix,
+            order,
+            proven_optimal,
+        } => {
+            println!(
+                "Board {} wins {} after {} draws{}",
+                board_number,
+                if goal == Goal::WinFirst {
+                    "first"
+                } else {
+                    "last"
+                },
+                prefix.len(),
+                if proven_optimal {
+                    ""
+                } else {
+                    " (node limit reached, a shorter order may exist)"
+                }
+            );
+            println!("Prefix:\n{}", to_draw_line(&prefix));
+            println!("Full order:\n{}", to_draw_line(&order));
+        }
+        SearchOutcome::Impossible => {
+            println!(
+                "Board {} can't win {:?} with these numbers",
+                board_number, goal
+            );
+        }
+        SearchOutcome::GaveUp => {
+            println!("Node limit reached before any order was found");
+        }
+    }
+
+    Ok(())
+}
+
+fn to_draw_line(numbers: &[i32]) -> String {
+    numbers
+        .iter()
+        .map(|number| number.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
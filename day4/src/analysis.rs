@@ -0,0 +1,201 @@
+use crate::board::BingoBoard;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
+
+/// How a single board fares over many shuffled draw orders.
+pub struct BoardOdds {
+    pub board_number: usize,
+    /// Share of games the board won first. Boards tied on the winning draw
+    /// split the win.
+    pub wins_first: f64,
+    /// Share of games the board won last, counting only games where every
+    /// board got a bingo.
+    pub wins_last: f64,
+    /// Share of games the board never got a bingo.
+    pub never_wins: f64,
+    /// Mean number of draws to bingo, over the games the board won. `None`
+    /// if it won none.
+    pub expected_draws: Option<f64>,
+    /// `histogram[n]` is how many games the board needed exactly `n` draws.
+    pub histogram: Vec<usize>,
+    pub trials: usize,
+}
+
+impl BoardOdds {
+    /// Share of games the board got a bingo within `draws` draws.
+    pub fn wins_within(&self, draws: usize) -> f64 {
+        let within: usize = self.histogram.iter().take(draws + 1).sum();
+        within as f64 / self.trials as f64
+    }
+}
+
+/// `draws` without repeats, in the order each number first comes up. A
+/// repeat marks nothing, so the odds are worked out over these.
+pub fn distinct_draws(draws: &[i32]) -> Vec<i32> {
+    let mut seen = HashSet::new();
+    draws
+        .iter()
+        .copied()
+        .filter(|number| seen.insert(*number))
+        .collect()
+}
+
+/// Number of draws each board needs for a bingo when the numbers come in
+/// `order`, or `None` if it never gets one.
+pub fn draws_to_win(boards: &[BingoBoard], order: &[i32]) -> Vec<Option<usize>> {
+    let mut drawn_at = HashMap::new();
+    for (i, &number) in order.iter().enumerate() {
+        drawn_at.entry(number).or_insert(i + 1);
+    }
+
+    boards
+        .iter()
+        .map(|board| {
+            board
+                .lines()
+                .iter()
+                .filter_map(|line| {
+                    line.iter()
+                        .map(|number| drawn_at.get(number).copied())
+                        .collect::<Option<Vec<usize>>>()
+                        .and_then(|drawn| drawn.into_iter().max())
+                })
+                .min()
+        })
+        .collect()
+}
+
+/// Plays `trials` games with `draws` shuffled and tallies how each board did.
+pub fn monte_carlo<R: Rng>(
+    boards: &[BingoBoard],
+    draws: &[i32],
+    trials: usize,
+    rng: &mut R,
+) -> Vec<BoardOdds> {
+    let mut first = vec![0.0; boards.len()];
+    let mut last = vec![0.0; boards.len()];
+    let mut never = vec![0; boards.len()];
+    let mut histograms = vec![vec![0; draws.len() + 1]; boards.len()];
+
+    let mut order = draws.to_vec();
+    for _ in 0..trials {
+        order.shuffle(rng);
+        let results = draws_to_win(boards, &order);
+
+        for (i, result) in results.iter().enumerate() {
+            match result {
+                Some(n) => histograms[i][*n] += 1,
+                None => never[i] += 1,
+            }
+        }
+
+        let won = results.iter().flatten();
+        if let Some(&earliest) = won.clone().min() {
+            share_win(&results, earliest, &mut first);
+        }
+        if results.iter().all(Option::is_some) {
+            if let Some(&latest) = won.max() {
+                share_win(&results, latest, &mut last);
+            }
+        }
+    }
+
+    boards
+        .iter()
+        .enumerate()
+        .map(|(i, board)| {
+            let won: usize = histograms[i].iter().sum();
+            let total_draws: usize = histograms[i]
+                .iter()
+                .enumerate()
+                .map(|(n, count)| n * count)
+                .sum();
+            BoardOdds {
+                board_number: board.number,
+                wins_first: first[i] / trials as f64,
+                wins_last: last[i] / trials as f64,
+                never_wins: never[i] as f64 / trials as f64,
+                expected_draws: if won > 0 {
+                    Some(total_draws as f64 / won as f64)
+                } else {
+                    None
+                },
+                histogram: histograms[i].clone(),
+                trials,
+            }
+        })
+        .collect()
+}
+
+fn share_win(results: &[Option<usize>], draw: usize, tally: &mut [f64]) {
+    let winners = results
+        .iter()
+        .enumerate()
+        .filter(|(_, result)| **result == Some(draw))
+        .map(|(i, _)| i)
+        .collect::<Vec<usize>>();
+    for i in winners.iter() {
+        tally[*i] += 1.0 / winners.len() as f64;
+    }
+}
+
+/// Exact probability that `board` has no bingo after `t` of `draws` are drawn
+/// in a uniformly random order, for every `t`. `result[t]` is that
+/// probability; it is 1 for `t = 0`.
+///
+/// Counts the t-subsets of the draws that complete no line by
+/// inclusion-exclusion over the 2^10 sets of lines: a t-subset containing
+/// every number of a set of lines covering `u` numbers can be picked in
+/// C(D - u, t - u) ways, and C(D - u, t - u) / C(D, t) is the product of
+/// (t - i) / (D - i) for i below u.
+pub fn exact_no_bingo(board: &BingoBoard, draws: &[i32]) -> Vec<f64> {
+    let pool = draws.iter().copied().collect::<HashSet<i32>>();
+    let total = pool.len();
+    let lines = board
+        .lines()
+        .into_iter()
+        .map(|line| line.into_iter().collect::<HashSet<i32>>())
+        .filter(|line| line.is_subset(&pool))
+        .collect::<Vec<HashSet<i32>>>();
+
+    // Sum of (-1)^|S| for every set of lines S, keyed by how many numbers
+    // the lines in S cover together.
+    let mut coefficients = vec![0i64; total + 1];
+    for subset in 0..(1usize << lines.len()) {
+        let mut covered = HashSet::new();
+        for (i, line) in lines.iter().enumerate() {
+            if subset & (1 << i) != 0 {
+                covered.extend(line.iter().copied());
+            }
+        }
+        let sign = if subset.count_ones() % 2 == 0 { 1 } else { -1 };
+        coefficients[covered.len()] += sign;
+    }
+
+    (0..=total)
+        .map(|t| {
+            let mut probability = 0.0;
+            for (covered, &coefficient) in coefficients.iter().enumerate() {
+                if coefficient == 0 || covered > t {
+                    continue;
+                }
+                let ratio =
+                    (0..covered).fold(1.0, |ratio, i| ratio * (t - i) as f64 / (total - i) as f64);
+                probability += coefficient as f64 * ratio;
+            }
+            probability.clamp(0.0, 1.0)
+        })
+        .collect()
+}
+
+/// Exact expected number of draws for `board` to get a bingo, or `None` if no
+/// line can ever be completed by `draws`.
+pub fn exact_expected_draws(board: &BingoBoard, draws: &[i32]) -> Option<f64> {
+    let no_bingo = exact_no_bingo(board, draws);
+    if *no_bingo.last()? > 1e-9 {
+        return None;
+    }
+
+    Some(no_bingo.iter().sum())
+}
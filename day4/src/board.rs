@@ -0,0 +1,264 @@
+use regex::Regex;
+use std::cmp::Eq;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+pub const BOARD_SIZE: usize = 5;
+
+#[derive(Clone, Copy)]
+pub struct BingoNumber {
+    pub number: i32,
+    pub marked: bool,
+}
+impl fmt::Display for BingoNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.number, if self.marked { "*" } else { "" })
+    }
+}
+
+/// A completed row or column on a board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WinningLine {
+    Row(usize),
+    Column(usize),
+}
+impl WinningLine {
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        match self {
+            WinningLine::Row(row) => *row == y,
+            WinningLine::Column(column) => *column == x,
+        }
+    }
+}
+impl fmt::Display for WinningLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WinningLine::Row(y) => write!(f, "row {}", y),
+            WinningLine::Column(x) => write!(f, "column {}", x),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct BingoBoard {
+    /// Position of the board in the input, starting from 1.
+    pub number: usize,
+    data: Vec<Vec<BingoNumber>>,
+    /// Where each number sits on the board, so marking doesn't scan every cell.
+    positions: HashMap<i32, Vec<(usize, usize)>>,
+    marked_in_row: [usize; BOARD_SIZE],
+    marked_in_column: [usize; BOARD_SIZE],
+    winning_line: Option<WinningLine>,
+    unmarked_sum: i32,
+}
+impl Hash for BingoBoard {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.number.hash(state);
+        self.fingerprint().hash(state);
+    }
+}
+impl PartialEq for BingoBoard {
+    fn eq(&self, other: &Self) -> bool {
+        self.number == other.number && self.fingerprint() == other.fingerprint()
+    }
+}
+impl Eq for BingoBoard {}
+
+impl fmt::Display for BingoBoard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let str = (0..BOARD_SIZE).fold(String::new(), |board_str, y| {
+            let line_str = (0..BOARD_SIZE).fold(String::new(), |line_str, x| {
+                let piece = self.get(x, y).unwrap();
+                line_str + &format!("{},", piece)
+            });
+
+            board_str + &format!("{}\n", line_str)
+        });
+
+        write!(f, "{}", str)
+    }
+}
+
+impl Default for BingoBoard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BingoBoard {
+    pub fn new() -> BingoBoard {
+        BingoBoard {
+            number: 0,
+            data: vec![
+                vec![
+                    BingoNumber {
+                        number: 0,
+                        marked: false
+                    };
+                    BOARD_SIZE
+                ];
+                BOARD_SIZE
+            ],
+            positions: HashMap::from([(0, all_positions())]),
+            marked_in_row: [0; BOARD_SIZE],
+            marked_in_column: [0; BOARD_SIZE],
+            winning_line: None,
+            unmarked_sum: 0,
+        }
+    }
+
+    pub fn from_string(str: &str) -> Result<BingoBoard, String> {
+        let regex = match Regex::new(r"(\s|\n)+") {
+            Ok(regex) => regex,
+            Err(e) => return Err(e.to_string()),
+        };
+        let numbers = regex.split(str.trim()).collect::<Vec<&str>>();
+        if numbers.len() != BOARD_SIZE * BOARD_SIZE {
+            return Err(format!(
+                "Expected {} numbers on a board, found {}",
+                BOARD_SIZE * BOARD_SIZE,
+                numbers.len()
+            ));
+        }
+
+        let mut board = BingoBoard::new();
+        for (i, number_str) in numbers.into_iter().enumerate() {
+            let x = i % BOARD_SIZE;
+            let y = ((i as f32) / BOARD_SIZE as f32) as usize;
+
+            let number = match number_str.parse::<i32>() {
+                Ok(number) => number,
+                Err(e) => return Err(e.to_string()),
+            };
+
+            if board.set(x, y, number).is_err() {
+                return Err("Failed to set number".to_string());
+            }
+        }
+
+        Ok(board)
+    }
+
+    pub fn with_number(mut self, number: usize) -> BingoBoard {
+        self.number = number;
+        self
+    }
+
+    /// FNV-1a hash of the numbers in reading order. Unlike `DefaultHasher`
+    /// it is the same on every platform and Rust version, and it ignores
+    /// whitespace and marks.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                for byte in self.data[x][y].number.to_le_bytes() {
+                    hash ^= byte as u64;
+                    hash = hash.wrapping_mul(0x100000001b3);
+                }
+            }
+        }
+        hash
+    }
+
+    /// Whether both boards hold the same numbers in the same cells.
+    pub fn same_numbers(&self, other: &BingoBoard) -> bool {
+        (0..BOARD_SIZE)
+            .all(|x| (0..BOARD_SIZE).all(|y| self.data[x][y].number == other.data[x][y].number))
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Result<&BingoNumber, ()> {
+        self.data.get(x).ok_or(())?.get(y).ok_or(())
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, number: i32) -> Result<(), ()> {
+        let piece = *self.get(x, y)?;
+        if let Some(positions) = self.positions.get_mut(&piece.number) {
+            positions.retain(|&position| position != (x, y));
+            if positions.is_empty() {
+                self.positions.remove(&piece.number);
+            }
+        }
+        self.positions.entry(number).or_default().push((x, y));
+        if !piece.marked {
+            self.unmarked_sum += number - piece.number;
+        }
+
+        self.get_mut(x, y)?.number = number;
+        Ok(())
+    }
+
+    fn get_mut(&mut self, x: usize, y: usize) -> Result<&mut BingoNumber, ()> {
+        self.data.get_mut(x).ok_or(())?.get_mut(y).ok_or(())
+    }
+
+    pub fn mark_numbers(&mut self, number: i32) -> Result<(), ()> {
+        let positions = match self.positions.get(&number) {
+            Some(positions) => positions,
+            None => return Ok(()),
+        };
+
+        for &(x, y) in positions {
+            let piece = self.data.get_mut(x).ok_or(())?.get_mut(y).ok_or(())?;
+            if piece.marked {
+                continue;
+            }
+            piece.marked = true;
+
+            self.unmarked_sum -= number;
+            self.marked_in_row[y] += 1;
+            self.marked_in_column[x] += 1;
+            if self.winning_line.is_none() {
+                if self.marked_in_row[y] == BOARD_SIZE {
+                    self.winning_line = Some(WinningLine::Row(y));
+                } else if self.marked_in_column[x] == BOARD_SIZE {
+                    self.winning_line = Some(WinningLine::Column(x));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Every row followed by every column, as the numbers they contain.
+    pub fn lines(&self) -> Vec<Vec<i32>> {
+        let rows =
+            (0..BOARD_SIZE).map(|y| (0..BOARD_SIZE).map(|x| self.data[x][y].number).collect());
+        let columns =
+            (0..BOARD_SIZE).map(|x| (0..BOARD_SIZE).map(|y| self.data[x][y].number).collect());
+        rows.chain(columns).collect()
+    }
+
+    /// Returns the row or column that completed first, if any.
+    pub fn winning_line(&self) -> Result<Option<WinningLine>, ()> {
+        Ok(self.winning_line)
+    }
+
+    pub fn has_bingo(&self) -> Result<bool, ()> {
+        Ok(self.winning_line.is_some())
+    }
+
+    pub fn get_unmarked(&self) -> Result<Vec<i32>, ()> {
+        let mut unmarked = Vec::new();
+        for x in 0..BOARD_SIZE {
+            for y in 0..BOARD_SIZE {
+                let piece = self.get(x, y)?;
+                if !piece.marked {
+                    unmarked.push(piece.number);
+                }
+            }
+        }
+
+        Ok(unmarked)
+    }
+
+    pub fn unmarked_sum(&self) -> Result<i32, ()> {
+        Ok(self.unmarked_sum)
+    }
+}
+
+fn all_positions() -> Vec<(usize, usize)> {
+    (0..BOARD_SIZE)
+        .flat_map(|x| (0..BOARD_SIZE).map(move |y| (x, y)))
+        .collect()
+}
@@ -0,0 +1,176 @@
+use crate::board::{BingoBoard, WinningLine};
+use std::collections::HashMap;
+use std::fmt;
+
+/// A single board reaching bingo during a game.
+#[derive(Clone)]
+pub struct BingoEvent {
+    /// Position of the board in the input, starting from 1.
+    pub board_number: usize,
+    /// Position of the winning number in the draw sequence, starting from 0.
+    pub draw_index: usize,
+    pub number: i32,
+    pub line: WinningLine,
+    pub unmarked_sum: i32,
+    pub score: i32,
+    /// The board as it looked when it won.
+    pub board: BingoBoard,
+}
+impl fmt::Display for BingoEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Board {} won on draw {} (number {}) with {}: unmarked sum={}, score={}",
+            self.board_number,
+            self.draw_index + 1,
+            self.number,
+            self.line,
+            self.unmarked_sum,
+            self.score
+        )
+    }
+}
+
+/// Every win of a game, in the order they happened.
+pub struct BingoResults {
+    events: Vec<BingoEvent>,
+}
+
+impl BingoResults {
+    pub fn first(&self) -> Option<&BingoEvent> {
+        self.events.first()
+    }
+
+    pub fn last(&self) -> Option<&BingoEvent> {
+        self.events.last()
+    }
+
+    /// The `n`th board to win, zero based.
+    pub fn nth(&self, n: usize) -> Option<&BingoEvent> {
+        self.events.get(n)
+    }
+
+    pub fn events(&self) -> &[BingoEvent] {
+        &self.events
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+pub struct BingoGame {
+    boards: Vec<BingoBoard>,
+    draws: Vec<i32>,
+}
+
+impl BingoGame {
+    /// Boards that haven't been numbered yet get their position in `boards`.
+    pub fn new(boards: Vec<BingoBoard>, draws: Vec<i32>) -> BingoGame {
+        let boards = boards
+            .into_iter()
+            .enumerate()
+            .map(|(i, board)| match board.number {
+                0 => board.with_number(i + 1),
+                _ => board,
+            })
+            .collect();
+        BingoGame { boards, draws }
+    }
+
+    pub fn from_input(input: &str) -> Result<BingoGame, String> {
+        let (draws, boards) = crate::parse_input(input)?;
+        Ok(BingoGame::new(boards, draws))
+    }
+
+    pub fn boards(&self) -> &[BingoBoard] {
+        &self.boards
+    }
+
+    pub fn draws(&self) -> &[i32] {
+        &self.draws
+    }
+
+    /// Groups of board numbers whose boards hold exactly the same numbers.
+    /// Such boards always win on the same draw.
+    pub fn duplicate_boards(&self) -> Vec<Vec<usize>> {
+        let mut by_fingerprint: HashMap<u64, Vec<Vec<&BingoBoard>>> = HashMap::new();
+        for board in self.boards.iter() {
+            let groups = by_fingerprint.entry(board.fingerprint()).or_default();
+            match groups.iter_mut().find(|group| group[0].same_numbers(board)) {
+                Some(group) => group.push(board),
+                None => groups.push(vec![board]),
+            }
+        }
+
+        let mut duplicates = by_fingerprint
+            .into_values()
+            .flatten()
+            .filter(|group| group.len() > 1)
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|board| board.number)
+                    .collect::<Vec<usize>>()
+            })
+            .collect::<Vec<Vec<usize>>>();
+        duplicates.sort();
+        duplicates
+    }
+
+    /// Plays the whole draw sequence, recording each board the first time
+    /// it gets a bingo. Stops once every board has won.
+    pub fn play(&self) -> Result<BingoResults, ()> {
+        Ok(self.run(self.draws.len(), true)?.1)
+    }
+
+    /// Plays the first `n_draws` draws and returns the boards as they look
+    /// afterwards, together with the wins so far. Boards keep being marked
+    /// after they have won.
+    pub fn play_until(&self, n_draws: usize) -> Result<(Vec<BingoBoard>, BingoResults), ()> {
+        self.run(n_draws.min(self.draws.len()), false)
+    }
+
+    fn run(
+        &self,
+        n_draws: usize,
+        stop_when_all_won: bool,
+    ) -> Result<(Vec<BingoBoard>, BingoResults), ()> {
+        let mut boards = self.boards.clone();
+        let mut has_won = vec![false; boards.len()];
+        let mut events = Vec::new();
+
+        for (draw_index, &number) in self.draws.iter().take(n_draws).enumerate() {
+            for (board_index, board) in boards.iter_mut().enumerate() {
+                board.mark_numbers(number)?;
+                if has_won[board_index] {
+                    continue;
+                }
+
+                if let Some(line) = board.winning_line()? {
+                    has_won[board_index] = true;
+                    let unmarked_sum = board.unmarked_sum()?;
+                    events.push(BingoEvent {
+                        board_number: board.number,
+                        draw_index,
+                        number,
+                        line,
+                        unmarked_sum,
+                        score: unmarked_sum * number,
+                        board: board.clone(),
+                    });
+                }
+            }
+
+            if stop_when_all_won && events.len() == boards.len() {
+                break;
+            }
+        }
+
+        Ok((boards, BingoResults { events }))
+    }
+}
@@ -0,0 +1,289 @@
+use crate::board::{BingoBoard, BOARD_SIZE};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValidationMode {
+    /// Every issue is an error.
+    Strict,
+    /// Broken draws and boards are skipped, everything else is reported as a
+    /// warning.
+    Lenient,
+}
+
+/// Something wrong with the puzzle input. Boards are numbered from 1 in input
+/// order, line numbers are one based like in an editor, draw positions are
+/// zero based.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputIssue {
+    InvalidDraw {
+        position: usize,
+        value: String,
+    },
+    RepeatedDraw {
+        position: usize,
+        first_position: usize,
+        number: i32,
+    },
+    UnusedDraw {
+        position: usize,
+        number: i32,
+    },
+    InvalidNumber {
+        board: usize,
+        line: usize,
+        value: String,
+    },
+    WrongCellCount {
+        board: usize,
+        line: usize,
+        found: usize,
+    },
+    WrongRowCount {
+        board: usize,
+        line: usize,
+        found: usize,
+    },
+    DuplicateNumber {
+        board: usize,
+        line: usize,
+        first_line: usize,
+        number: i32,
+    },
+}
+
+impl InputIssue {
+    /// Whether the issue is a valid draw that repeats or is on no board,
+    /// which doesn't affect the game.
+    fn is_draw_issue(&self) -> bool {
+        matches!(
+            self,
+            InputIssue::RepeatedDraw { .. } | InputIssue::UnusedDraw { .. }
+        )
+    }
+
+    /// Whether lenient parsing has to drop the draw or board.
+    fn is_fatal(&self) -> bool {
+        matches!(
+            self,
+            InputIssue::InvalidDraw { .. }
+                | InputIssue::InvalidNumber { .. }
+                | InputIssue::WrongCellCount { .. }
+                | InputIssue::WrongRowCount { .. }
+        )
+    }
+}
+
+impl fmt::Display for InputIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputIssue::InvalidDraw { position, value } => {
+                write!(f, "line 1: draw {} '{}' is not a number", position, value)
+            }
+            InputIssue::RepeatedDraw {
+                position,
+                first_position,
+                number,
+            } => write!(
+                f,
+                "line 1: draw {} repeats {} already drawn at {}",
+                position, number, first_position
+            ),
+            InputIssue::UnusedDraw { position, number } => {
+                write!(f, "line 1: draw {} ({}) is on no board", position, number)
+            }
+            InputIssue::InvalidNumber { board, line, value } => write!(
+                f,
+                "line {}: board {} has '{}' which is not a number",
+                line, board, value
+            ),
+            InputIssue::WrongCellCount { board, line, found } => write!(
+                f,
+                "line {}: board {} has {} cells in a row, expected {}",
+                line, board, found, BOARD_SIZE
+            ),
+            InputIssue::WrongRowCount { board, line, found } => write!(
+                f,
+                "line {}: board {} has {} rows, expected {}",
+                line, board, found, BOARD_SIZE
+            ),
+            InputIssue::DuplicateNumber {
+                board,
+                line,
+                first_line,
+                number,
+            } => write!(
+                f,
+                "line {}: board {} repeats {} from line {}",
+                line, board, number, first_line
+            ),
+        }
+    }
+}
+
+pub struct ParsedInput {
+    pub draws: Vec<i32>,
+    pub boards: Vec<BingoBoard>,
+    /// Issues that were tolerated in lenient mode.
+    pub warnings: Vec<InputIssue>,
+}
+
+/// Parses the puzzle input: the comma separated draw line, followed by blank
+/// line separated boards. Draws that repeat or are on no board don't change
+/// the game and are accepted, like the puzzle does; any other issue is an
+/// error. `validate --strict` rejects those draws too.
+pub fn parse_input(input: &str) -> Result<(Vec<i32>, Vec<BingoBoard>), String> {
+    let parsed = parse_input_with_mode(input, ValidationMode::Lenient)
+        .expect("Lenient parsing doesn't fail");
+    let errors = parsed
+        .warnings
+        .iter()
+        .filter(|issue| !issue.is_draw_issue())
+        .map(|issue| issue.to_string())
+        .collect::<Vec<String>>();
+    if !errors.is_empty() {
+        return Err(errors.join("\n"));
+    }
+    Ok((parsed.draws, parsed.boards))
+}
+
+pub fn parse_input_with_mode(
+    input: &str,
+    mode: ValidationMode,
+) -> Result<ParsedInput, Vec<InputIssue>> {
+    let mut issues = Vec::new();
+
+    let draw_line = input.lines().next().unwrap_or("");
+    let mut draws = Vec::new();
+    let mut draw_positions = Vec::new();
+    let mut first_drawn_at = HashMap::new();
+    for (position, value) in draw_line.split(',').enumerate() {
+        let number = match value.trim().parse::<i32>() {
+            Ok(number) => number,
+            Err(_) => {
+                issues.push(InputIssue::InvalidDraw {
+                    position,
+                    value: value.to_string(),
+                });
+                continue;
+            }
+        };
+        if let Some(&first_position) = first_drawn_at.get(&number) {
+            issues.push(InputIssue::RepeatedDraw {
+                position,
+                first_position,
+                number,
+            });
+        } else {
+            first_drawn_at.insert(number, position);
+        }
+        draws.push(number);
+        draw_positions.push(position);
+    }
+
+    let mut boards = Vec::new();
+    let mut on_any_board = HashSet::new();
+    for (board_index, rows) in board_chunks(input).iter().enumerate() {
+        let board_number = board_index + 1;
+        let mut board_issues = Vec::new();
+        let mut first_seen_on = HashMap::new();
+        let mut cells = Vec::new();
+
+        for &(line, row) in rows {
+            let values = row.split_whitespace().collect::<Vec<&str>>();
+            if values.len() != BOARD_SIZE {
+                board_issues.push(InputIssue::WrongCellCount {
+                    board: board_number,
+                    line,
+                    found: values.len(),
+                });
+            }
+            for value in values {
+                let number = match value.parse::<i32>() {
+                    Ok(number) => number,
+                    Err(_) => {
+                        board_issues.push(InputIssue::InvalidNumber {
+                            board: board_number,
+                            line,
+                            value: value.to_string(),
+                        });
+                        continue;
+                    }
+                };
+                if let Some(&first_line) = first_seen_on.get(&number) {
+                    board_issues.push(InputIssue::DuplicateNumber {
+                        board: board_number,
+                        line,
+                        first_line,
+                        number,
+                    });
+                } else {
+                    first_seen_on.insert(number, line);
+                }
+                cells.push(number);
+            }
+        }
+        if rows.len() != BOARD_SIZE {
+            board_issues.push(InputIssue::WrongRowCount {
+                board: board_number,
+                line: rows[0].0,
+                found: rows.len(),
+            });
+        }
+
+        if !board_issues.iter().any(InputIssue::is_fatal) {
+            on_any_board.extend(cells);
+            let board_str = rows
+                .iter()
+                .map(|(_, row)| *row)
+                .collect::<Vec<&str>>()
+                .join("\n");
+            match BingoBoard::from_string(&board_str) {
+                Ok(board) => boards.push(board.with_number(board_number)),
+                Err(e) => board_issues.push(InputIssue::InvalidNumber {
+                    board: board_number,
+                    line: rows[0].0,
+                    value: e,
+                }),
+            }
+        }
+        issues.append(&mut board_issues);
+    }
+
+    for (&position, &number) in draw_positions.iter().zip(draws.iter()) {
+        if !on_any_board.contains(&number) {
+            issues.push(InputIssue::UnusedDraw { position, number });
+        }
+    }
+
+    match mode {
+        ValidationMode::Strict if !issues.is_empty() => Err(issues),
+        _ => Ok(ParsedInput {
+            draws,
+            boards,
+            warnings: issues,
+        }),
+    }
+}
+
+/// Splits everything after the draw line into boards of (line number, row)
+/// pairs, separated by blank lines.
+fn board_chunks(input: &str) -> Vec<Vec<(usize, &str)>> {
+    let mut chunks = Vec::new();
+    let mut current = Vec::new();
+    for (i, line) in input.lines().enumerate().skip(1) {
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                chunks.push(current);
+                current = Vec::new();
+            }
+        } else {
+            current.push((i + 1, line));
+        }
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+
+    chunks
+}
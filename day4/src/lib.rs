@@ -0,0 +1,11 @@
+#![allow(clippy::result_unit_err)]
+
+pub mod analysis;
+pub mod board;
+pub mod game;
+pub mod input;
+pub mod search;
+
+pub use board::{BingoBoard, BingoNumber, WinningLine, BOARD_SIZE};
+pub use game::{BingoEvent, BingoGame, BingoResults};
+pub use input::{parse_input, parse_input_with_mode, InputIssue, ParsedInput, ValidationMode};
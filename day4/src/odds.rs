@@ -0,0 +1,103 @@
+use day4::analysis::{
+    distinct_draws, draws_to_win, exact_expected_draws, exact_no_bingo, monte_carlo,
+};
+use day4::BingoGame;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::env;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+
+/// Shuffles the draws many times to see how lucky the real draw order was.
+///
+/// Usage: odds [--trials N] [--seed S] [input file]
+///
+/// Repeated draws are dropped first, so draw counts are of distinct numbers.
+fn main() -> io::Result<()> {
+    let mut trials = 10_000;
+    let mut seed = None;
+    let mut path = "./input.txt".to_string();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--trials" => trials = args.next().unwrap().parse::<usize>().unwrap(),
+            "--seed" => seed = Some(args.next().unwrap().parse::<u64>().unwrap()),
+            _ => path = arg,
+        }
+    }
+
+    let file = fs::File::open(&path)?;
+    let mut reader = io::BufReader::new(file);
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+
+    let game = BingoGame::from_input(&input).unwrap();
+    let boards = game.boards();
+    let draws = &distinct_draws(game.draws());
+
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let odds = monte_carlo(boards, draws, trials, &mut rng);
+    let real = draws_to_win(boards, draws);
+
+    println!(
+        "{:>5} {:>8} {:>8} {:>8} {:>8} {:>6} {:>10} {:>11}",
+        "board", "P(first)", "P(last)", "E[draws]", "exact", "real", "MC P(<=)", "exact P(<=)"
+    );
+    for (i, board_odds) in odds.iter().enumerate() {
+        let exact = exact_expected_draws(&boards[i], draws)
+            .map(|expected| format!("{:.2}", expected))
+            .unwrap_or_else(|| "never".to_string());
+        let expected = board_odds
+            .expected_draws
+            .map(|expected| format!("{:.2}", expected))
+            .unwrap_or_else(|| "never".to_string());
+        let (real_draws, mc_luck, exact_luck) = match real[i] {
+            Some(n) => (
+                n.to_string(),
+                format!("{:.4}", board_odds.wins_within(n)),
+                match exact_no_bingo(&boards[i], draws).get(n) {
+                    Some(no_bingo) => format!("{:.4}", 1.0 - no_bingo),
+                    None => "-".to_string(),
+                },
+            ),
+            None => ("never".to_string(), "-".to_string(), "-".to_string()),
+        };
+        println!(
+            "{:>5} {:>8.4} {:>8.4} {:>8} {:>8} {:>6} {:>10} {:>11}",
+            board_odds.board_number,
+            board_odds.wins_first,
+            board_odds.wins_last,
+            expected,
+            exact,
+            real_draws,
+            mc_luck,
+            exact_luck
+        );
+    }
+
+    let results = game.play().unwrap();
+    if let (Some(first), Some(last)) = (results.first(), results.last()) {
+        let index_of = |board_number: usize| {
+            odds.iter()
+                .position(|o| o.board_number == board_number)
+                .unwrap()
+        };
+        let (first_index, last_index) = (index_of(first.board_number), index_of(last.board_number));
+        println!(
+            "Real game: board {} won first after {} draws (P={:.4}), board {} won last after {} draws (P={:.4}), {} trials",
+            first.board_number,
+            real[first_index].unwrap(),
+            odds[first_index].wins_first,
+            last.board_number,
+            real[last_index].unwrap(),
+            odds[last_index].wins_last,
+            trials
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,340 @@
+use crate::board::BingoBoard;
+use crate::game::BingoGame;
+use std::collections::hash_map::{Entry, HashMap};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Goal {
+    /// The board gets a bingo before every other board.
+    WinFirst,
+    /// Every other board gets a bingo before the board does.
+    WinLast,
+}
+
+pub enum SearchOutcome {
+    Found {
+        /// The draws up to and including the one that decides the game.
+        prefix: Vec<i32>,
+        /// `prefix` followed by the remaining numbers, in input order.
+        order: Vec<i32>,
+        /// Whether no shorter prefix exists. `false` when the search hit its
+        /// node limit before it could rule shorter prefixes out.
+        proven_optimal: bool,
+    },
+    /// No draw order reaches the goal.
+    Impossible,
+    /// The node limit was reached before any draw order was found.
+    GaveUp,
+}
+
+/// Set of numbers from the draw pool, stored as bits indexed by the position
+/// of the number in the pool.
+#[derive(Clone, PartialEq, Eq)]
+struct NumberSet {
+    words: Vec<u64>,
+}
+
+impl NumberSet {
+    fn new(size: usize) -> NumberSet {
+        NumberSet {
+            words: vec![0; size.div_ceil(64)],
+        }
+    }
+
+    fn insert(&mut self, i: usize) {
+        self.words[i / 64] |= 1 << (i % 64);
+    }
+
+    fn contains(&self, i: usize) -> bool {
+        self.words[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    fn union(&self, other: &NumberSet) -> NumberSet {
+        NumberSet {
+            words: self
+                .words
+                .iter()
+                .zip(other.words.iter())
+                .map(|(a, b)| a | b)
+                .collect(),
+        }
+    }
+
+    fn is_subset(&self, other: &NumberSet) -> bool {
+        self.words
+            .iter()
+            .zip(other.words.iter())
+            .all(|(a, b)| a & !b == 0)
+    }
+
+    /// Number of elements of `self` missing from `other`.
+    fn missing_from(&self, other: &NumberSet) -> usize {
+        self.words
+            .iter()
+            .zip(other.words.iter())
+            .map(|(a, b)| (a & !b).count_ones() as usize)
+            .sum()
+    }
+
+    fn indices(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.words.len() * 64).filter(move |&i| self.contains(i))
+    }
+}
+
+/// The lines of a board that can be completed from the pool.
+fn pool_lines(board: &BingoBoard, index: &HashMap<i32, usize>, size: usize) -> Vec<NumberSet> {
+    board
+        .lines()
+        .iter()
+        .filter_map(|line| {
+            let mut set = NumberSet::new(size);
+            for number in line {
+                set.insert(*index.get(number)?);
+            }
+            Some(set)
+        })
+        .collect()
+}
+
+/// Looks for a draw order, using the numbers in `draws`, that makes board
+/// `board_number` reach `goal` in as few draws as possible.
+pub fn search(
+    boards: &[BingoBoard],
+    draws: &[i32],
+    board_number: usize,
+    goal: Goal,
+    node_limit: usize,
+) -> Result<SearchOutcome, String> {
+    let mut pool = Vec::new();
+    let mut index = HashMap::new();
+    for &number in draws {
+        if let Entry::Vacant(entry) = index.entry(number) {
+            entry.insert(pool.len());
+            pool.push(number);
+        }
+    }
+
+    let target = boards
+        .iter()
+        .find(|board| board.number == board_number)
+        .ok_or_else(|| format!("There is no board {}", board_number))?;
+    let target_lines = pool_lines(target, &index, pool.len());
+    let other_lines = boards
+        .iter()
+        .filter(|board| board.number != board_number)
+        .map(|board| pool_lines(board, &index, pool.len()))
+        .collect::<Vec<Vec<NumberSet>>>();
+
+    let found = match goal {
+        Goal::WinFirst => win_first(&target_lines, &other_lines).map(|prefix| (prefix, true)),
+        Goal::WinLast => {
+            let mut search = LastSearch {
+                pool_size: pool.len(),
+                target_lines: &target_lines,
+                other_lines: &other_lines,
+                nodes: 0,
+                node_limit,
+                best: None,
+            };
+            search.run();
+            let exhausted = search.nodes <= node_limit;
+            match search.best {
+                Some(prefix) => Some((prefix, exhausted)),
+                None if exhausted => None,
+                None => return Ok(SearchOutcome::GaveUp),
+            }
+        }
+    };
+
+    let (prefix, proven_optimal) = match found {
+        Some(found) => found,
+        None => return Ok(SearchOutcome::Impossible),
+    };
+    let prefix = prefix.into_iter().map(|i| pool[i]).collect::<Vec<i32>>();
+    let order = prefix
+        .iter()
+        .copied()
+        .chain(
+            pool.iter()
+                .copied()
+                .filter(|number| !prefix.contains(number)),
+        )
+        .collect::<Vec<i32>>();
+
+    verify(boards, &order, board_number, goal, prefix.len())?;
+
+    Ok(SearchOutcome::Found {
+        prefix,
+        order,
+        proven_optimal,
+    })
+}
+
+/// The board wins first by drawing exactly one of its lines, as long as no
+/// other board has a line made only of those numbers. If every line of the
+/// board is covered like that, the other board always wins no later, so it
+/// can't win first at all.
+fn win_first(target_lines: &[NumberSet], other_lines: &[Vec<NumberSet>]) -> Option<Vec<usize>> {
+    target_lines
+        .iter()
+        .filter(|line| {
+            !other_lines
+                .iter()
+                .flatten()
+                .any(|other| other.is_subset(line))
+        })
+        .min_by_key(|line| line.len())
+        .map(|line| line.indices().collect())
+}
+
+/// Branch and bound over which line each other board completes. The final
+/// draw `finish` completes the target line `line`, so everything drawn
+/// before it must contain the rest of `line`, a full line of every other
+/// board, no full line of the target and not `finish` itself.
+struct LastSearch<'a> {
+    pool_size: usize,
+    target_lines: &'a [NumberSet],
+    other_lines: &'a [Vec<NumberSet>],
+    nodes: usize,
+    node_limit: usize,
+    /// Shortest prefix found so far, final draw last.
+    best: Option<Vec<usize>>,
+}
+
+impl<'a> LastSearch<'a> {
+    fn run(&mut self) {
+        if self.other_lines.iter().any(|lines| lines.is_empty()) {
+            return;
+        }
+
+        let mut candidates = Vec::new();
+        for line in self.target_lines.iter() {
+            for finish in line.indices() {
+                let mut drawn = NumberSet::new(self.pool_size);
+                for i in line.indices().filter(|&i| i != finish) {
+                    drawn.insert(i);
+                }
+                candidates.push((drawn, finish));
+            }
+        }
+        candidates.sort_by_key(|(drawn, _)| drawn.len());
+
+        for (drawn, finish) in candidates {
+            if self.nodes > self.node_limit {
+                return;
+            }
+            if self.target_complete(&drawn) {
+                continue;
+            }
+            self.descend(drawn, finish);
+        }
+    }
+
+    fn target_complete(&self, drawn: &NumberSet) -> bool {
+        self.target_lines.iter().any(|line| line.is_subset(drawn))
+    }
+
+    fn best_len(&self) -> usize {
+        self.best.as_ref().map_or(usize::MAX, |best| best.len())
+    }
+
+    fn descend(&mut self, drawn: NumberSet, finish: usize) {
+        self.nodes += 1;
+        if self.nodes > self.node_limit {
+            return;
+        }
+
+        // The board still without a bingo that needs the most new numbers
+        // gives both the lower bound and the board to branch on.
+        let mut hardest: Option<(usize, usize)> = None;
+        for (board, lines) in self.other_lines.iter().enumerate() {
+            let needed = lines
+                .iter()
+                .filter(|line| !line.contains(finish))
+                .map(|line| line.missing_from(&drawn))
+                .min();
+            match needed {
+                None => return,
+                Some(0) => continue,
+                Some(needed) => {
+                    if hardest.is_none_or(|(_, most)| needed > most) {
+                        hardest = Some((board, needed));
+                    }
+                }
+            }
+        }
+
+        let (board, needed) = match hardest {
+            Some(hardest) => hardest,
+            None => {
+                if drawn.len() + 1 < self.best_len() {
+                    let mut prefix = drawn.indices().collect::<Vec<usize>>();
+                    prefix.push(finish);
+                    self.best = Some(prefix);
+                }
+                return;
+            }
+        };
+        if drawn.len() + needed + 1 >= self.best_len() {
+            return;
+        }
+
+        let mut options = self.other_lines[board]
+            .iter()
+            .filter(|line| !line.contains(finish))
+            .map(|line| drawn.union(line))
+            .filter(|next| !self.target_complete(next))
+            .collect::<Vec<NumberSet>>();
+        options.sort_by_key(|next| next.len());
+        options.dedup();
+        for next in options {
+            self.descend(next, finish);
+        }
+    }
+}
+
+/// Plays the constructed order through `BingoGame` to make sure it does what
+/// the search claims.
+fn verify(
+    boards: &[BingoBoard],
+    order: &[i32],
+    board_number: usize,
+    goal: Goal,
+    draws_needed: usize,
+) -> Result<(), String> {
+    let game = BingoGame::new(boards.to_vec(), order.to_vec());
+    let results = game
+        .play()
+        .map_err(|_| "Failed to play the constructed order".to_string())?;
+    let event = match goal {
+        Goal::WinFirst => results.first(),
+        Goal::WinLast => results.last(),
+    };
+    let ok = match event {
+        Some(event) => {
+            event.board_number == board_number
+                && event.draw_index + 1 == draws_needed
+                && results
+                    .events()
+                    .iter()
+                    .filter(|other| other.draw_index == event.draw_index)
+                    .count()
+                    == 1
+        }
+        None => false,
+    };
+    if ok {
+        Ok(())
+    } else {
+        Err(format!(
+            "Constructed order doesn't make board {} win {:?}",
+            board_number, goal
+        ))
+    }
+}
@@ -0,0 +1,229 @@
+use ansi_term::Colour::{Black, Green, Red, Yellow};
+use ansi_term::Style;
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{
+    self, disable_raw_mode, enable_raw_mode, Clear, ClearType, EnterAlternateScreen,
+    LeaveAlternateScreen,
+};
+use day4::{BingoBoard, BingoGame, BOARD_SIZE};
+use std::env;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+
+const CELL_WIDTH: usize = 3;
+const BOARD_GAP: usize = 3;
+const HEADER_LINES: usize = 4;
+
+/// Steps through the draws with every board on screen.
+///
+/// Usage: tui [--at N] [input file]
+///
+/// Keys: left/right (or p/n) step a draw, home/end jump to the start or end,
+/// up/down scroll the boards, q quits. With `--at N` the state after N draws
+/// is printed once instead.
+fn main() -> io::Result<()> {
+    let mut at = None;
+    let mut path = "./input.txt".to_string();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--at" => at = Some(args.next().unwrap().parse::<usize>().unwrap()),
+            _ => path = arg,
+        }
+    }
+
+    let file = fs::File::open(&path)?;
+    let mut reader = io::BufReader::new(file);
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+
+    let game = BingoGame::from_input(&input).unwrap();
+
+    if let Some(at) = at {
+        let (width, _) = terminal::size().unwrap_or((120, 40));
+        for line in render(&game, at, 0, width as usize, usize::MAX) {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+
+    let mut stdout = io::stdout();
+    enable_raw_mode()?;
+    execute!(stdout, EnterAlternateScreen, Hide)?;
+    let result = run(&game, &mut stdout);
+    execute!(stdout, Show, LeaveAlternateScreen)?;
+    disable_raw_mode()?;
+
+    result
+}
+
+fn run(game: &BingoGame, stdout: &mut io::Stdout) -> io::Result<()> {
+    let mut step = 0;
+    let mut scroll = 0;
+    loop {
+        let (width, height) = terminal::size()?;
+        let lines = render(game, step, scroll, width as usize, height as usize);
+        execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
+        write!(stdout, "{}", lines.join("\r\n"))?;
+        stdout.flush()?;
+
+        if let Event::Key(KeyEvent { code, modifiers }) = event::read()? {
+            match code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
+                KeyCode::Right | KeyCode::Char('n') | KeyCode::Char(' ') => {
+                    step = (step + 1).min(game.draws().len())
+                }
+                KeyCode::Left | KeyCode::Char('p') => step = step.saturating_sub(1),
+                KeyCode::Home => step = 0,
+                KeyCode::End => step = game.draws().len(),
+                KeyCode::Down | KeyCode::Char('j') => scroll += 1,
+                KeyCode::Up | KeyCode::Char('k') => scroll = scroll.saturating_sub(1),
+                _ => (),
+            }
+        }
+    }
+}
+
+/// The screen after `step` draws, with the first `scroll` rows of boards
+/// skipped, cut to fit `width` x `height`.
+fn render(
+    game: &BingoGame,
+    step: usize,
+    scroll: usize,
+    width: usize,
+    height: usize,
+) -> Vec<String> {
+    let (boards, results) = game.play_until(step).unwrap();
+    let drawn = &game.draws()[..step.min(game.draws().len())];
+    let current = drawn.last().copied();
+
+    let mut lines = Vec::new();
+    lines.push(format!(
+        "Draw {}/{}: {}   (left/right step, home/end jump, up/down scroll, q quit)",
+        drawn.len(),
+        game.draws().len(),
+        match current {
+            Some(number) => Red.bold().paint(number.to_string()).to_string(),
+            None => "-".to_string(),
+        }
+    ));
+    lines.push(format!(
+        "Drawn: {}",
+        drawn
+            .iter()
+            .map(|number| number.to_string())
+            .collect::<Vec<String>>()
+            .join(",")
+    ));
+    lines.push(format!(
+        "Won ({}/{}): {}",
+        results.len(),
+        boards.len(),
+        results
+            .events()
+            .iter()
+            .map(|event| format!(
+                "#{} (draw {}, score {})",
+                event.board_number,
+                event.draw_index + 1,
+                event.score
+            ))
+            .collect::<Vec<String>>()
+            .join(", ")
+    ));
+    lines.push(String::new());
+
+    let board_width = BOARD_SIZE * CELL_WIDTH;
+    let per_row = ((width + BOARD_GAP) / (board_width + BOARD_GAP)).max(1);
+    let won_at = |board: &BingoBoard| {
+        results
+            .events()
+            .iter()
+            .find(|event| event.board_number == board.number)
+            .map(|event| event.draw_index + 1)
+    };
+
+    for chunk in boards.chunks(per_row).skip(scroll) {
+        let mut title = String::new();
+        for board in chunk {
+            let label = match won_at(board) {
+                Some(draw) => format!("#{} won@{}", board.number, draw),
+                None => format!("#{}", board.number),
+            };
+            let padded = format!("{:<width$}", label, width = board_width + BOARD_GAP);
+            title += &match won_at(board) {
+                Some(_) => Yellow.bold().paint(padded).to_string(),
+                None => padded,
+            };
+        }
+        lines.push(title);
+
+        for y in 0..BOARD_SIZE {
+            let mut row = String::new();
+            for board in chunk {
+                for x in 0..BOARD_SIZE {
+                    row += &cell(board, x, y, current);
+                }
+                row += &" ".repeat(BOARD_GAP);
+            }
+            lines.push(row);
+        }
+        lines.push(String::new());
+
+        if lines.len() >= height {
+            break;
+        }
+    }
+
+    lines.truncate(height.max(HEADER_LINES));
+    lines.iter().map(|line| clip(line, width)).collect()
+}
+
+/// Cuts `line` to its first `width` visible characters. Colour codes don't
+/// take up any width, and the colour is reset if anything was cut.
+fn clip(line: &str, width: usize) -> String {
+    let mut clipped = String::new();
+    let mut visible = 0;
+    let mut chars = line.chars();
+    while let Some(char) = chars.next() {
+        if char == '\x1b' {
+            clipped.push(char);
+            for code in chars.by_ref() {
+                clipped.push(code);
+                if code.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else if visible == width {
+            return clipped + "\x1b[0m";
+        } else {
+            clipped.push(char);
+            visible += 1;
+        }
+    }
+    clipped
+}
+
+fn cell(board: &BingoBoard, x: usize, y: usize, current: Option<i32>) -> String {
+    let piece = board.get(x, y).unwrap();
+    let text = format!("{:>width$}", piece.number, width = CELL_WIDTH);
+    let in_winning_line = board
+        .winning_line()
+        .unwrap()
+        .is_some_and(|line| line.contains(x, y));
+
+    let style = if !piece.marked {
+        Style::new()
+    } else if in_winning_line {
+        Black.on(Yellow).bold()
+    } else if Some(piece.number) == current {
+        Red.bold().underline()
+    } else {
+        Green.bold()
+    };
+    style.paint(text).to_string()
+}
@@ -0,0 +1,51 @@
+use day4::{parse_input_with_mode, ValidationMode};
+use std::env;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::process;
+
+/// Checks a bingo input and lists every problem found.
+///
+/// Usage: validate [--strict] [input file]
+///
+/// Broken draws and boards are skipped and reported unless `--strict` makes
+/// every issue, even a draw on no board, an error.
+fn main() -> io::Result<()> {
+    let mut mode = ValidationMode::Lenient;
+    let mut path = "./input.txt".to_string();
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--lenient" => mode = ValidationMode::Lenient,
+            "--strict" => mode = ValidationMode::Strict,
+            _ => path = arg,
+        }
+    }
+
+    let file = fs::File::open(&path)?;
+    let mut reader = io::BufReader::new(file);
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+
+    match parse_input_with_mode(&input, mode) {
+        Ok(parsed) => {
+            for warning in parsed.warnings.iter() {
+                println!("warning: {}", warning);
+            }
+            println!(
+                "{}: {} draws, {} boards",
+                path,
+                parsed.draws.len(),
+                parsed.boards.len()
+            );
+        }
+        Err(issues) => {
+            for issue in issues.iter() {
+                println!("error: {}", issue);
+            }
+            process::exit(1);
+        }
+    }
+
+    Ok(())
+}
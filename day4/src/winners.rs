@@ -0,0 +1,41 @@
+use day4::BingoGame;
+use std::env;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+
+/// Plays the full game and prints every board in the order it won.
+///
+/// Usage: winners [input file]
+fn main() -> io::Result<()> {
+    let path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| "./input.txt".to_string());
+    let file = fs::File::open(path)?;
+    let mut reader = io::BufReader::new(file);
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+
+    let game = BingoGame::from_input(&input).unwrap();
+    let results = game.play().unwrap();
+
+    for group in game.duplicate_boards() {
+        let numbers = group
+            .iter()
+            .map(|number| number.to_string())
+            .collect::<Vec<String>>();
+        println!("Boards {} are identical", numbers.join(", "));
+    }
+
+    for (place, event) in results.events().iter().enumerate() {
+        println!("#{}: {}", place + 1, event);
+    }
+    println!(
+        "{} of {} boards won within {} draws",
+        results.len(),
+        game.boards().len(),
+        game.draws().len()
+    );
+
+    Ok(())
+}
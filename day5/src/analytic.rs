@@ -0,0 +1,272 @@
+use crate::line::Line;
+use crate::point::Point;
+use std::cmp::{max, min};
+use std::collections::HashMap;
+
+/// Direction of the infinite line a segment lies on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Kind {
+    /// `y = c`, points are indexed by x.
+    Horizontal,
+    /// `x = c`, points are indexed by y.
+    Vertical,
+    /// `y = x + c`, points are indexed by x.
+    Diagonal,
+    /// `y = -x + c`, points are indexed by x.
+    AntiDiagonal,
+}
+
+/// The infinite line a segment lies on. Segments on the same carrier can
+/// overlap along a stretch, segments on different carriers meet in at most
+/// one point.
+///
+/// `c` is an i128 because `y - x` and `y + x` of an i64 point don't fit in
+/// an i64, and neither do the crossings of carriers far apart.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Carrier {
+    kind: Kind,
+    c: i128,
+}
+impl Carrier {
+    /// The point at position `t`, `None` if it is off the i64 grid.
+    fn point_at(&self, t: i64) -> Option<Point> {
+        let (x, y) = match self.kind {
+            Kind::Horizontal => (t as i128, self.c),
+            Kind::Vertical => (self.c, t as i128),
+            Kind::Diagonal => (t as i128, t as i128 + self.c),
+            Kind::AntiDiagonal => (t as i128, self.c - t as i128),
+        };
+        Some(Point {
+            x: i64::try_from(x).ok()?,
+            y: i64::try_from(y).ok()?,
+        })
+    }
+
+    fn position_of(&self, point: Point) -> i64 {
+        match self.kind {
+            Kind::Vertical => point.y,
+            _ => point.x,
+        }
+    }
+
+    /// Position along `self` where it crosses `other`, if they cross on a
+    /// lattice point of the i64 grid.
+    fn crossing(&self, other: &Carrier) -> Option<i64> {
+        use Kind::*;
+        let (x, y) = match (self.kind, other.kind) {
+            (a, b) if a == b => return None,
+            (Horizontal, Vertical) => (other.c, self.c),
+            (Vertical, Horizontal) => (self.c, other.c),
+            (Horizontal, Diagonal) | (Diagonal, Horizontal) => {
+                let (h, d) = if self.kind == Horizontal {
+                    (self, other)
+                } else {
+                    (other, self)
+                };
+                (h.c - d.c, h.c)
+            }
+            (Horizontal, AntiDiagonal) | (AntiDiagonal, Horizontal) => {
+                let (h, a) = if self.kind == Horizontal {
+                    (self, other)
+                } else {
+                    (other, self)
+                };
+                (a.c - h.c, h.c)
+            }
+            (Vertical, Diagonal) | (Diagonal, Vertical) => {
+                let (v, d) = if self.kind == Vertical {
+                    (self, other)
+                } else {
+                    (other, self)
+                };
+                (v.c, v.c + d.c)
+            }
+            (Vertical, AntiDiagonal) | (AntiDiagonal, Vertical) => {
+                let (v, a) = if self.kind == Vertical {
+                    (self, other)
+                } else {
+                    (other, self)
+                };
+                (v.c, a.c - v.c)
+            }
+            (Diagonal, AntiDiagonal) | (AntiDiagonal, Diagonal) => {
+                let (d, a) = if self.kind == Diagonal {
+                    (self, other)
+                } else {
+                    (other, self)
+                };
+                if (a.c - d.c) % 2 != 0 {
+                    return None;
+                }
+                let x = (a.c - d.c) / 2;
+                (x, x + d.c)
+            }
+            _ => unreachable!(),
+        };
+        let point = Point {
+            x: i64::try_from(x).ok()?,
+            y: i64::try_from(y).ok()?,
+        };
+        Some(self.position_of(point))
+    }
+}
+
+/// Splits a segment into its carrier and the inclusive range of positions it
+/// covers. Single points are treated as horizontal.
+fn carrier_of(line: &Line) -> Result<(Carrier, (i64, i64)), String> {
+    let delta = line.p2 - line.p1;
+    let kind = if delta.y == 0 {
+        Kind::Horizontal
+    } else if delta.x == 0 {
+        Kind::Vertical
+    } else if delta.x == delta.y {
+        Kind::Diagonal
+    } else if delta.x == -delta.y {
+        Kind::AntiDiagonal
+    } else {
+        return Err(format!(
+            "Line {} is not horizontal, vertical or diagonal",
+            line
+        ));
+    };
+    let c = match kind {
+        Kind::Horizontal => line.p1.y as i128,
+        Kind::Vertical => line.p1.x as i128,
+        Kind::Diagonal => line.p1.y as i128 - line.p1.x as i128,
+        Kind::AntiDiagonal => line.p1.y as i128 + line.p1.x as i128,
+    };
+    let carrier = Carrier { kind, c };
+    let (a, b) = (carrier.position_of(line.p1), carrier.position_of(line.p2));
+
+    Ok((carrier, (min(a, b), max(a, b))))
+}
+
+/// Coverage of a single carrier, as sorted and disjoint inclusive ranges.
+struct Coverage {
+    /// Positions covered by at least one segment.
+    once: Vec<(i64, i64)>,
+    /// Positions covered by at least two segments.
+    twice: Vec<(i64, i64)>,
+}
+impl Coverage {
+    fn new(ranges: &[(i64, i64)]) -> Coverage {
+        let mut events = Vec::with_capacity(ranges.len() * 2);
+        for &(start, end) in ranges {
+            events.push((start as i128, 1));
+            events.push((end as i128 + 1, -1));
+        }
+        events.sort();
+
+        let mut once = Vec::new();
+        let mut twice = Vec::new();
+        let mut depth = 0;
+        let mut once_start = 0;
+        let mut twice_start = 0;
+        for (position, change) in events {
+            let before = depth;
+            depth += change;
+            if before < 1 && depth >= 1 {
+                once_start = position;
+            } else if before >= 1 && depth < 1 {
+                push_range(&mut once, once_start, position - 1);
+            }
+            if before < 2 && depth >= 2 {
+                twice_start = position;
+            } else if before >= 2 && depth < 2 {
+                push_range(&mut twice, twice_start, position - 1);
+            }
+        }
+
+        Coverage { once, twice }
+    }
+
+    fn contains(ranges: &[(i64, i64)], t: i64) -> bool {
+        let i = ranges.partition_point(|&(_, end)| end < t);
+        i < ranges.len() && ranges[i].0 <= t
+    }
+}
+
+/// Adds a range, merging it with the previous one if they touch, since events
+/// at the same position may close and reopen a range.
+fn push_range(ranges: &mut Vec<(i64, i64)>, start: i128, end: i128) {
+    if start > end {
+        return;
+    }
+    let (start, end) = (start as i64, end as i64);
+    match ranges.last_mut() {
+        Some(last) if last.1.saturating_add(1) >= start => last.1 = max(last.1, end),
+        _ => ranges.push((start, end)),
+    }
+}
+
+/// Counts the points covered by at least two lines without visiting every
+/// point of every line. Only horizontal, vertical and 45° lines are
+/// supported.
+///
+/// Overlaps along a carrier come from a sweep over the segment endpoints on
+/// that carrier. Points where different carriers cross are found pairwise
+/// between the covered stretches of each carrier; such a point counts when
+/// it is covered on two or more carriers, and is subtracted when it would
+/// otherwise be counted once per carrier it overlaps on.
+pub fn count_overlaps(lines: &[Line]) -> Result<u128, String> {
+    let mut by_carrier: HashMap<Carrier, Vec<(i64, i64)>> = HashMap::new();
+    for line in lines {
+        let (carrier, range) = carrier_of(line)?;
+        by_carrier.entry(carrier).or_default().push(range);
+    }
+
+    let mut carriers = by_carrier
+        .into_iter()
+        .map(|(carrier, ranges)| (carrier, Coverage::new(&ranges)))
+        .collect::<Vec<(Carrier, Coverage)>>();
+    carriers.sort_by_key(|(carrier, _)| *carrier);
+
+    let mut total: u128 = carriers
+        .iter()
+        .flat_map(|(_, coverage)| coverage.twice.iter())
+        .map(|&(start, end)| (end as i128 - start as i128 + 1) as u128)
+        .sum();
+
+    // Every lattice point covered on at least two carriers, with the carriers.
+    let mut crossings: HashMap<Point, Vec<usize>> = HashMap::new();
+    for (i, (a, a_coverage)) in carriers.iter().enumerate() {
+        for (j, (b, b_coverage)) in carriers.iter().enumerate().skip(i + 1) {
+            let t = match a.crossing(b) {
+                Some(t) => t,
+                None => continue,
+            };
+            if !Coverage::contains(&a_coverage.once, t) {
+                continue;
+            }
+            let Some(point) = a.point_at(t) else {
+                continue;
+            };
+            if !Coverage::contains(&b_coverage.once, b.position_of(point)) {
+                continue;
+            }
+            let on = crossings.entry(point).or_default();
+            for k in [i, j] {
+                if !on.contains(&k) {
+                    on.push(k);
+                }
+            }
+        }
+    }
+
+    for (point, on) in crossings {
+        let twice = on
+            .iter()
+            .filter(|&&k| {
+                let (carrier, coverage) = &carriers[k];
+                Coverage::contains(&coverage.twice, carrier.position_of(point))
+            })
+            .count();
+        if twice == 0 {
+            total += 1;
+        } else {
+            total -= (twice - 1) as u128;
+        }
+    }
+
+    Ok(total)
+}
@@ -0,0 +1,185 @@
+use crate::analytic::count_overlaps;
+use crate::filter::{parse_box, parse_slopes, LineFilter};
+use crate::grid::Storage;
+use crate::line::{unsupported_slopes, Line, Rasterization, Slope};
+use crate::vents::HydrothermalVentData;
+use std::env;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::process;
+use std::time::Instant;
+
+/// Where the lines come from, which of them take part and how they are
+/// stored and rasterized.
+pub struct CommonOptions {
+    pub path: String,
+    pub filter: LineFilter,
+    pub storage: Storage,
+    pub mode: Rasterization,
+    /// In puzzle mode, leave out lines with other slopes with a warning
+    /// instead of failing.
+    pub skip_unsupported: bool,
+}
+impl CommonOptions {
+    pub fn new(filter: LineFilter, storage: Storage) -> CommonOptions {
+        CommonOptions {
+            path: "./input.txt".to_string(),
+            filter,
+            storage,
+            mode: Rasterization::Puzzle,
+            skip_unsupported: false,
+        }
+    }
+
+    /// Handles `arg` if it is a common flag, taking its value from `args`.
+    /// Anything that isn't a flag is the input file. Returns `false` for
+    /// flags it doesn't know.
+    pub fn parse_arg<I: Iterator<Item = String>>(
+        &mut self,
+        arg: &str,
+        args: &mut I,
+    ) -> Result<bool, String> {
+        let mut value = || args.next().ok_or(format!("{} needs a value", arg));
+        match arg {
+            "--lines" => self.filter.slopes = parse_slopes(&value()?)?,
+            "--within" => self.filter.within = Some(parse_box(&value()?)?),
+            "--min-length" => {
+                self.filter.min_length = Some(value()?.parse::<i64>().map_err(|e| e.to_string())?)
+            }
+            "--max-length" => {
+                self.filter.max_length = Some(value()?.parse::<i64>().map_err(|e| e.to_string())?)
+            }
+            "--storage" => self.storage = value()?.parse::<Storage>()?,
+            "--mode" => self.mode = value()?.parse::<Rasterization>()?,
+            "--skip-unsupported" => self.skip_unsupported = true,
+            _ if arg.starts_with("--") => return Ok(false),
+            _ => self.path = arg.to_string(),
+        }
+        Ok(true)
+    }
+
+    /// Reads and parses the input file and keeps the lines the filter
+    /// matches.
+    pub fn read_lines(&self) -> Result<Vec<Line>, String> {
+        let file = fs::File::open(&self.path).map_err(|e| format!("{}: {}", self.path, e))?;
+        let mut reader = io::BufReader::new(file);
+        let mut input = String::new();
+        reader
+            .read_to_string(&mut input)
+            .map_err(|e| e.to_string())?;
+
+        let mut lines = crate::parse_lines(&input)?;
+        if self.mode == Rasterization::Puzzle && self.skip_unsupported {
+            for (i, line) in unsupported_slopes(&lines) {
+                eprintln!(
+                    "warning: skipping line {} ({}), unsupported slope",
+                    i + 1,
+                    line
+                );
+            }
+            lines.retain(|line| line.slope() != Slope::Other);
+        }
+
+        Ok(self.filter.apply(&lines))
+    }
+
+    pub fn vent_data(&self, lines: &[Line]) -> Result<HydrothermalVentData, String> {
+        HydrothermalVentData::with_mode(lines, self.storage, self.mode)
+    }
+}
+
+/// The overlap solver behind part 1 and part 2, which only differ in the
+/// default line filter.
+///
+/// Extra flags: `--at-least K` counts points covered by at least K lines
+/// instead of 2, `--solver analytic` counts without rasterizing (puzzle
+/// slopes only), `--check` runs both solvers and compares them, and
+/// `--no-map` skips printing the map.
+pub fn solve(filter: LineFilter) -> io::Result<()> {
+    let mut options = CommonOptions::new(filter, Storage::Dense);
+    let mut at_least = 2;
+    let mut analytic = false;
+    let mut check = false;
+    let mut print_map = true;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--at-least" => at_least = args.next().unwrap().parse::<i32>().unwrap(),
+            "--solver" => match args.next().unwrap().as_str() {
+                "raster" => analytic = false,
+                "analytic" => analytic = true,
+                solver => panic!("Unknown solver '{}', expected raster or analytic", solver),
+            },
+            "--check" => check = true,
+            "--no-map" => print_map = false,
+            _ => {
+                if !options.parse_arg(&arg, &mut args).unwrap() {
+                    panic!("Unknown flag {}", arg);
+                }
+            }
+        }
+    }
+    if at_least < 1 {
+        panic!(
+            "--at-least counts covered points, K must be at least 1, not {}",
+            at_least
+        );
+    }
+    if (analytic || check) && at_least != 2 {
+        panic!("The analytic solver only counts points covered by at least 2 lines");
+    }
+
+    let lines = match options.read_lines() {
+        Ok(lines) => lines,
+        Err(e) => panic!("{}", e),
+    };
+
+    let mut analytic_count = None;
+    if analytic || check {
+        let start = Instant::now();
+        let count = match count_overlaps(&lines) {
+            Ok(count) => count,
+            Err(e) => panic!("{}", e),
+        };
+        eprintln!("analytic solver, {:?}", start.elapsed());
+        analytic_count = Some(count);
+    }
+
+    if analytic && !check {
+        println!("Number of overlapping lines={}", analytic_count.unwrap());
+        return Ok(());
+    }
+
+    let start = Instant::now();
+    let data = match options.vent_data(&lines) {
+        Ok(data) => data,
+        Err(e) => panic!("{}", e),
+    };
+    eprintln!(
+        "{} storage, {} bytes, {:?}",
+        data.grid().name(),
+        data.memory_usage(),
+        start.elapsed()
+    );
+    if print_map && data.grid().name() == "dense" {
+        data.print_map();
+    }
+
+    let count = data.count_at_least(at_least);
+    if at_least == 2 {
+        println!("Number of overlapping lines={}", count);
+    } else {
+        println!("Points covered by at least {} lines={}", at_least, count);
+    }
+
+    if let Some(analytic_count) = analytic_count {
+        if analytic_count != count as u128 {
+            println!("Mismatch! The analytic solver counted {}", analytic_count);
+            process::exit(1);
+        }
+        println!("The analytic solver agrees");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use crate::line::{Line, Slope};
+use crate::point::Point;
+
+/// Which of the input lines take part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineFilter {
+    /// Slopes to keep. Empty keeps every slope.
+    pub slopes: Vec<Slope>,
+    /// Only keep lines that lie entirely within this box, inclusive.
+    pub within: Option<(Point, Point)>,
+    /// Length is the number of steps from one end to the other along the
+    /// longer axis, so a single point has length 0.
+    pub min_length: Option<i64>,
+    pub max_length: Option<i64>,
+}
+impl LineFilter {
+    pub fn all() -> LineFilter {
+        LineFilter {
+            slopes: Vec::new(),
+            within: None,
+            min_length: None,
+            max_length: None,
+        }
+    }
+
+    /// Horizontal and vertical lines, as in part 1.
+    pub fn straight() -> LineFilter {
+        LineFilter {
+            slopes: vec![Slope::Horizontal, Slope::Vertical],
+            ..LineFilter::all()
+        }
+    }
+
+    pub fn matches(&self, line: &Line) -> bool {
+        if !self.slopes.is_empty() && !self.slopes.contains(&line.slope()) {
+            return false;
+        }
+
+        if let Some((low, high)) = self.within {
+            let inside = |point: Point| {
+                point.x >= low.x && point.x <= high.x && point.y >= low.y && point.y <= high.y
+            };
+            if !inside(line.p1) || !inside(line.p2) {
+                return false;
+            }
+        }
+
+        let length = length(line);
+        self.min_length.is_none_or(|min| length >= min)
+            && self.max_length.is_none_or(|max| length <= max)
+    }
+
+    pub fn apply(&self, lines: &[Line]) -> Vec<Line> {
+        lines
+            .iter()
+            .filter(|line| self.matches(line))
+            .copied()
+            .collect()
+    }
+}
+
+fn length(line: &Line) -> i64 {
+    let delta = line.p2 - line.p1;
+    delta.x.abs().max(delta.y.abs())
+}
+
+/// Parses a comma separated list of line types. `straight` is horizontal and
+/// vertical, `all` keeps everything.
+pub fn parse_slopes(str: &str) -> Result<Vec<Slope>, String> {
+    let mut slopes = Vec::new();
+    for name in str.split(',').map(|name| name.trim()) {
+        match name {
+            "all" => return Ok(Vec::new()),
+            "straight" => slopes.extend([Slope::Horizontal, Slope::Vertical]),
+            _ => slopes.push(name.parse::<Slope>()?),
+        }
+    }
+    slopes.dedup();
+    Ok(slopes)
+}
+
+/// Parses `X1,Y1,X2,Y2` into the smallest and largest corner.
+pub fn parse_box(str: &str) -> Result<(Point, Point), String> {
+    let numbers = str
+        .split(',')
+        .map(|n| n.trim().parse::<i64>().map_err(|e| e.to_string()))
+        .collect::<Result<Vec<i64>, String>>()?;
+    if numbers.len() != 4 {
+        return Err(format!(
+            "Couldn't parse box '{}', expected X1,Y1,X2,Y2",
+            str
+        ));
+    }
+    Ok((
+        Point {
+            x: numbers[0].min(numbers[2]),
+            y: numbers[1].min(numbers[3]),
+        },
+        Point {
+            x: numbers[0].max(numbers[2]),
+            y: numbers[1].max(numbers[3]),
+        },
+    ))
+}
@@ -0,0 +1,207 @@
+use crate::point::Point;
+use std::collections::HashMap;
+use std::mem;
+use std::str::FromStr;
+
+/// Storage for the number of lines covering each point. Points that were
+/// never set read as 0.
+pub trait Grid {
+    fn get(&self, x: i64, y: i64) -> Option<&i32>;
+    fn set(&mut self, x: i64, y: i64, number: i32) -> Result<(), String>;
+    /// Every point with a non-zero value.
+    fn cells(&self) -> Box<dyn Iterator<Item = (Point, i32)> + '_>;
+    /// Approximate number of bytes used by the storage.
+    fn memory_usage(&self) -> usize;
+    fn name(&self) -> &'static str;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Storage {
+    Dense,
+    Sparse,
+    /// Dense when the bounding box has at most `DENSE_CELL_LIMIT` cells.
+    Auto,
+}
+
+/// Largest bounding box, in cells, that `Storage::Auto` stores densely.
+pub const DENSE_CELL_LIMIT: u64 = 1 << 26;
+
+impl FromStr for Storage {
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Storage, String> {
+        match str {
+            "dense" => Ok(Storage::Dense),
+            "sparse" => Ok(Storage::Sparse),
+            "auto" => Ok(Storage::Auto),
+            _ => Err(format!(
+                "Unknown storage '{}', expected dense, sparse or auto",
+                str
+            )),
+        }
+    }
+}
+
+impl Storage {
+    /// Creates a grid covering `min` to `max`, inclusive.
+    pub fn create(&self, min: Point, max: Point) -> Result<Box<dyn Grid>, String> {
+        match self {
+            Storage::Dense => Ok(Box::new(DenseGrid::new(min, max)?)),
+            Storage::Sparse => Ok(Box::new(SparseGrid::new())),
+            Storage::Auto if area(min, max) <= DENSE_CELL_LIMIT as u128 => {
+                Ok(Box::new(DenseGrid::new(min, max)?))
+            }
+            Storage::Auto => Ok(Box::new(SparseGrid::new())),
+        }
+    }
+}
+
+/// Number of points from `min` to `max`, inclusive.
+fn area(min: Point, max: Point) -> u128 {
+    let width = (max.x as i128 - min.x as i128 + 1).max(0) as u128;
+    let height = (max.y as i128 - min.y as i128 + 1).max(0) as u128;
+    width * height
+}
+
+/// One `i32` per point of a fixed bounding box, which may start anywhere.
+pub struct DenseGrid {
+    origin: Point,
+    data: Vec<Vec<i32>>,
+}
+impl DenseGrid {
+    pub fn new(min: Point, max: Point) -> Result<DenseGrid, String> {
+        if area(min, max) > DENSE_CELL_LIMIT as u128 * 16 {
+            return Err(format!(
+                "A dense grid from {} to {} is too large, use sparse storage",
+                min, max
+            ));
+        }
+        let width = usize::try_from(max.x - min.x + 1)
+            .map_err(|_| format!("Invalid grid bounds {} to {}", min, max))?;
+        let height = usize::try_from(max.y - min.y + 1)
+            .map_err(|_| format!("Invalid grid bounds {} to {}", min, max))?;
+
+        Ok(DenseGrid {
+            origin: min,
+            data: vec![vec![0; height]; width],
+        })
+    }
+
+    fn index(&self, x: i64, y: i64) -> Option<(usize, usize)> {
+        let x = usize::try_from(x.checked_sub(self.origin.x)?).ok()?;
+        let y = usize::try_from(y.checked_sub(self.origin.y)?).ok()?;
+        Some((x, y))
+    }
+
+    fn get_mut(&mut self, x: i64, y: i64) -> Option<&mut i32> {
+        let (x, y) = self.index(x, y)?;
+        match self.data.get_mut(x) {
+            Some(column) => column.get_mut(y),
+            None => None,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.data.first().map_or(0, |column| column.len())
+    }
+}
+impl Grid for DenseGrid {
+    fn get(&self, x: i64, y: i64) -> Option<&i32> {
+        let (x, y) = self.index(x, y)?;
+        match self.data.get(x) {
+            Some(column) => column.get(y),
+            None => None,
+        }
+    }
+
+    fn set(&mut self, x: i64, y: i64, number: i32) -> Result<(), String> {
+        match self.get_mut(x, y) {
+            Some(cell) => {
+                *cell = number;
+                Ok(())
+            }
+            None => Err(format!("Couldn't find position ({}, {})", x, y)),
+        }
+    }
+
+    fn cells(&self) -> Box<dyn Iterator<Item = (Point, i32)> + '_> {
+        Box::new(self.data.iter().enumerate().flat_map(move |(x, column)| {
+            column
+                .iter()
+                .enumerate()
+                .filter(|(_, value)| **value != 0)
+                .map(move |(y, value)| {
+                    (
+                        Point {
+                            x: self.origin.x + x as i64,
+                            y: self.origin.y + y as i64,
+                        },
+                        *value,
+                    )
+                })
+        }))
+    }
+
+    fn memory_usage(&self) -> usize {
+        mem::size_of::<Self>()
+            + self.data.capacity() * mem::size_of::<Vec<i32>>()
+            + self
+                .data
+                .iter()
+                .map(|column| column.capacity() * mem::size_of::<i32>())
+                .sum::<usize>()
+    }
+
+    fn name(&self) -> &'static str {
+        "dense"
+    }
+}
+
+/// Only stores points that have been set, so the coordinates can be anywhere
+/// in the `i64` range.
+pub struct SparseGrid {
+    data: HashMap<Point, i32>,
+}
+impl SparseGrid {
+    pub fn new() -> SparseGrid {
+        SparseGrid {
+            data: HashMap::new(),
+        }
+    }
+}
+impl Default for SparseGrid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Grid for SparseGrid {
+    fn get(&self, x: i64, y: i64) -> Option<&i32> {
+        Some(self.data.get(&Point { x, y }).unwrap_or(&0))
+    }
+
+    fn set(&mut self, x: i64, y: i64, number: i32) -> Result<(), String> {
+        if number == 0 {
+            self.data.remove(&Point { x, y });
+        } else {
+            self.data.insert(Point { x, y }, number);
+        }
+        Ok(())
+    }
+
+    fn cells(&self) -> Box<dyn Iterator<Item = (Point, i32)> + '_> {
+        Box::new(self.data.iter().map(|(point, value)| (*point, *value)))
+    }
+
+    fn memory_usage(&self) -> usize {
+        // A hashbrown table stores one control byte next to every slot.
+        mem::size_of::<Self>() + self.data.capacity() * (mem::size_of::<(Point, i32)>() + 1)
+    }
+
+    fn name(&self) -> &'static str {
+        "sparse"
+    }
+}
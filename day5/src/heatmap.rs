@@ -0,0 +1,331 @@
+use crate::line::{Line, Rasterization};
+use crate::point::Point;
+use crate::vents::HydrothermalVentData;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::str::FromStr;
+
+/// Largest image, in pixels, `render` agrees to allocate.
+pub const MAX_PIXELS: u64 = 1 << 28;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+impl FromStr for Rgb {
+    type Err = String;
+
+    /// Parses `#rrggbb` or `rrggbb`.
+    fn from_str(str: &str) -> Result<Rgb, String> {
+        let hex = str.trim().trim_start_matches('#');
+        if hex.len() != 6 {
+            return Err(format!("Couldn't parse colour '{}', expected #rrggbb", str));
+        }
+        let channel = |i: usize| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .map_err(|e| format!("Couldn't parse colour '{}': {}", str, e))
+        };
+        Ok(Rgb(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+/// Colours to interpolate between, from no coverage to the most covered
+/// point.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColourRamp {
+    stops: Vec<Rgb>,
+}
+impl ColourRamp {
+    pub fn new(stops: Vec<Rgb>) -> Result<ColourRamp, String> {
+        if stops.len() < 2 {
+            return Err("A colour ramp needs at least two colours".to_string());
+        }
+        Ok(ColourRamp { stops })
+    }
+
+    pub fn grey() -> ColourRamp {
+        ColourRamp {
+            stops: vec![Rgb(0, 0, 0), Rgb(255, 255, 255)],
+        }
+    }
+
+    /// Black through red and yellow to white.
+    pub fn hot() -> ColourRamp {
+        ColourRamp {
+            stops: vec![
+                Rgb(0, 0, 0),
+                Rgb(200, 0, 0),
+                Rgb(255, 200, 0),
+                Rgb(255, 255, 255),
+            ],
+        }
+    }
+
+    /// Dark blue through cyan and yellow to red.
+    pub fn thermal() -> ColourRamp {
+        ColourRamp {
+            stops: vec![
+                Rgb(0, 0, 40),
+                Rgb(0, 160, 220),
+                Rgb(250, 230, 0),
+                Rgb(220, 0, 0),
+            ],
+        }
+    }
+
+    /// The colour at `t`, which is clamped to 0..=1.
+    pub fn colour(&self, t: f64) -> Rgb {
+        let t = t.clamp(0.0, 1.0) * (self.stops.len() - 1) as f64;
+        let i = (t.floor() as usize).min(self.stops.len() - 2);
+        let f = t - i as f64;
+        let (a, b) = (self.stops[i], self.stops[i + 1]);
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * f).round() as u8;
+        Rgb(mix(a.0, b.0), mix(a.1, b.1), mix(a.2, b.2))
+    }
+}
+impl FromStr for ColourRamp {
+    type Err = String;
+
+    /// A built-in ramp by name, or comma separated `#rrggbb` colours.
+    fn from_str(str: &str) -> Result<ColourRamp, String> {
+        match str {
+            "grey" | "gray" => Ok(ColourRamp::grey()),
+            "hot" => Ok(ColourRamp::hot()),
+            "thermal" => Ok(ColourRamp::thermal()),
+            _ => ColourRamp::new(
+                str.split(',')
+                    .map(|colour| colour.parse::<Rgb>())
+                    .collect::<Result<Vec<Rgb>, String>>()?,
+            ),
+        }
+    }
+}
+
+/// How coverage counts map onto the colour ramp.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scale {
+    Linear,
+    Sqrt,
+    Log,
+}
+impl Scale {
+    /// Position of `value` on the ramp when `max` is the highest count.
+    fn position(&self, value: i32, max: i32) -> f64 {
+        if value <= 0 || max <= 0 {
+            return 0.0;
+        }
+        let (value, max) = (value as f64, max as f64);
+        match self {
+            Scale::Linear => value / max,
+            Scale::Sqrt => value.sqrt() / max.sqrt(),
+            Scale::Log => (1.0 + value).ln() / (1.0 + max).ln(),
+        }
+    }
+}
+impl FromStr for Scale {
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Scale, String> {
+        match str {
+            "linear" => Ok(Scale::Linear),
+            "sqrt" => Ok(Scale::Sqrt),
+            "log" => Ok(Scale::Log),
+            _ => Err(format!(
+                "Unknown scale '{}', expected linear, sqrt or log",
+                str
+            )),
+        }
+    }
+}
+
+pub struct HeatmapOptions {
+    pub ramp: ColourRamp,
+    pub scale: Scale,
+    /// Side of the square of pixels drawn for each point.
+    pub pixel_size: u32,
+    /// Colour to draw the input line segments in, on top of the heatmap.
+    /// They are drawn one pixel wide through the middle of each point, so
+    /// the heatmap stays visible with a `pixel_size` of 3 or more.
+    pub overlay: Option<Rgb>,
+    /// Smallest and largest corner of the area to draw, inclusive. Defaults
+    /// to the bounding box of the lines.
+    pub crop: Option<(Point, Point)>,
+}
+impl Default for HeatmapOptions {
+    fn default() -> Self {
+        HeatmapOptions {
+            ramp: ColourRamp::hot(),
+            scale: Scale::Linear,
+            pixel_size: 1,
+            overlay: None,
+            crop: None,
+        }
+    }
+}
+
+/// An RGB image, row by row from the top left.
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pixels: Vec<u8>,
+}
+impl Image {
+    fn new(width: u32, height: u32, background: Rgb) -> Image {
+        Image {
+            width,
+            height,
+            pixels: [background.0, background.1, background.2]
+                .repeat(width as usize * height as usize),
+        }
+    }
+
+    fn set(&mut self, x: i64, y: i64, colour: Rgb) {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return;
+        }
+        let i = (y as usize * self.width as usize + x as usize) * 3;
+        self.pixels[i] = colour.0;
+        self.pixels[i + 1] = colour.1;
+        self.pixels[i + 2] = colour.2;
+    }
+
+    /// Binary PPM (P6), readable by most image tools without any library.
+    pub fn write_ppm<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width, self.height)?;
+        writer.write_all(&self.pixels)
+    }
+
+    pub fn write_png<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut png_writer = encoder.write_header().map_err(io::Error::other)?;
+        png_writer
+            .write_image_data(&self.pixels)
+            .map_err(io::Error::other)
+    }
+
+    /// Writes a PNG if `path` ends in `.png`, a PPM otherwise.
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        let file = fs::File::create(path)?;
+        let mut writer = io::BufWriter::new(file);
+        if path.to_lowercase().ends_with(".png") {
+            self.write_png(writer)
+        } else {
+            self.write_ppm(&mut writer)?;
+            writer.flush()
+        }
+    }
+}
+
+/// Draws the coverage of `data` as a heatmap.
+pub fn render(data: &HydrothermalVentData, options: &HeatmapOptions) -> Result<Image, String> {
+    let (low, high) = match options.crop.or_else(|| data.bounds()) {
+        Some(bounds) => bounds,
+        None => return Err("There are no lines to draw".to_string()),
+    };
+    if high.x < low.x || high.y < low.y {
+        return Err(format!("Empty crop from {} to {}", low, high));
+    }
+    // A crop can be 2^64 points across, so sizes are worked out in i128.
+    let size = options.pixel_size.max(1) as i128;
+    let width = (high.x as i128 - low.x as i128 + 1) * size;
+    let height = (high.y as i128 - low.y as i128 + 1) * size;
+    if width.saturating_mul(height) > MAX_PIXELS as i128 {
+        return Err(format!(
+            "A {}x{} image is too large, crop it or use a smaller pixel size",
+            width, height
+        ));
+    }
+
+    let mut image = Image::new(width as u32, height as u32, options.ramp.colour(0.0));
+    let cells = data
+        .grid()
+        .cells()
+        .filter(|(point, _)| {
+            point.x >= low.x && point.x <= high.x && point.y >= low.y && point.y <= high.y
+        })
+        .collect::<Vec<(Point, i32)>>();
+    let max = cells.iter().map(|(_, value)| *value).max().unwrap_or(0);
+
+    // Cells are inside the crop, so their pixels fit in an i64.
+    for (point, value) in cells {
+        let colour = options.ramp.colour(options.scale.position(value, max));
+        let (left, top) = (
+            (point.x as i128 - low.x as i128) * size,
+            (point.y as i128 - low.y as i128) * size,
+        );
+        for dy in 0..size {
+            for dx in 0..size {
+                image.set((left + dx) as i64, (top + dy) as i64, colour);
+            }
+        }
+    }
+
+    if let Some(colour) = options.overlay {
+        let centre = |point: Point| {
+            (
+                (point.x as i128 - low.x as i128) * size + size / 2,
+                (point.y as i128 - low.y as i128) * size + size / 2,
+            )
+        };
+        for line in data.lines() {
+            let (from, to) = (centre(line.p1), centre(line.p2));
+            draw_clipped(&mut image, from, to, colour)?;
+        }
+    }
+
+    Ok(image)
+}
+
+/// Draws the segment from pixel `from` to pixel `to`, which may lie far
+/// outside the image. It is halved until each piece is either wholly inside
+/// the image, and drawn with Bresenham's algorithm, or wholly outside its
+/// bounding box, and dropped, so long lines aren't walked pixel by pixel
+/// outside the crop. Rounding the halfway points moves a piece by at most a
+/// pixel.
+fn draw_clipped(
+    image: &mut Image,
+    from: (i128, i128),
+    to: (i128, i128),
+    colour: Rgb,
+) -> Result<(), String> {
+    let (width, height) = (image.width as i128, image.height as i128);
+    let inside = |(x, y): (i128, i128)| x >= 0 && x < width && y >= 0 && y < height;
+    if from.0.max(to.0) < 0
+        || from.0.min(to.0) >= width
+        || from.1.max(to.1) < 0
+        || from.1.min(to.1) >= height
+    {
+        return Ok(());
+    }
+
+    if inside(from) && inside(to) {
+        let pixels = Line {
+            p1: Point {
+                x: from.0 as i64,
+                y: from.1 as i64,
+            },
+            p2: Point {
+                x: to.0 as i64,
+                y: to.1 as i64,
+            },
+        };
+        for pixel in pixels.points(Rasterization::Bresenham)? {
+            image.set(pixel.x, pixel.y, colour);
+        }
+        return Ok(());
+    }
+
+    let middle = ((from.0 + to.0).div_euclid(2), (from.1 + to.1).div_euclid(2));
+    if middle == from || middle == to {
+        // Neighbouring pixels, at least one of them outside.
+        for end in [from, to] {
+            if inside(end) {
+                image.set(end.0 as i64, end.1 as i64, colour);
+            }
+        }
+        return Ok(());
+    }
+    draw_clipped(image, from, middle, colour)?;
+    draw_clipped(image, middle, to, colour)
+}
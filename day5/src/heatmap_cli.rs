@@ -0,0 +1,55 @@
+use day5::cli::CommonOptions;
+use day5::filter::parse_box;
+use day5::heatmap::{render, ColourRamp, HeatmapOptions, Scale};
+use day5::{LineFilter, Storage};
+use std::env;
+use std::io;
+
+/// Renders the vent map as a heatmap image.
+///
+/// Usage: heatmap [--output FILE.png|FILE.ppm] [--ramp hot|grey|thermal|#rrggbb,...]
+///                [--scale linear|sqrt|log] [--pixel N] [--overlay #rrggbb]
+///                [--crop X1,Y1,X2,Y2]
+///                [--lines horizontal,vertical,diagonal,other|straight|all]
+///                [--within X1,Y1,X2,Y2] [--min-length N] [--max-length N]
+///                [--storage dense|sparse|auto] [--mode puzzle|bresenham|lattice]
+///                [--skip-unsupported] [input file]
+fn main() -> io::Result<()> {
+    let mut options = HeatmapOptions::default();
+    let mut output = "heatmap.png".to_string();
+    let mut common = CommonOptions::new(LineFilter::all(), Storage::Auto);
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--output" => output = args.next().unwrap(),
+            "--ramp" => options.ramp = args.next().unwrap().parse::<ColourRamp>().unwrap(),
+            "--scale" => options.scale = args.next().unwrap().parse::<Scale>().unwrap(),
+            "--pixel" => options.pixel_size = args.next().unwrap().parse::<u32>().unwrap(),
+            "--overlay" => options.overlay = Some(args.next().unwrap().parse().unwrap()),
+            "--crop" => options.crop = Some(parse_box(&args.next().unwrap()).unwrap()),
+            _ => {
+                if !common.parse_arg(&arg, &mut args).unwrap() {
+                    panic!("Unknown flag {}", arg);
+                }
+            }
+        }
+    }
+
+    let lines = match common.read_lines() {
+        Ok(lines) => lines,
+        Err(e) => panic!("{}", e),
+    };
+    let data = match common.vent_data(&lines) {
+        Ok(data) => data,
+        Err(e) => panic!("{}", e),
+    };
+
+    let image = match render(&data, &options) {
+        Ok(image) => image,
+        Err(e) => panic!("{}", e),
+    };
+    image.save(&output)?;
+    println!("Wrote {}x{} image to {}", image.width, image.height, output);
+
+    Ok(())
+}
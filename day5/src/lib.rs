@@ -0,0 +1,22 @@
+pub mod analytic;
+pub mod cli;
+pub mod filter;
+pub mod grid;
+pub mod heatmap;
+pub mod line;
+pub mod point;
+pub mod vents;
+
+pub use filter::LineFilter;
+pub use grid::{DenseGrid, Grid, SparseGrid, Storage};
+pub use line::{unsupported_slopes, Line, LineIterator, Rasterization, Slope};
+pub use point::Point;
+pub use vents::HydrothermalVentData;
+
+pub fn parse_lines(input: &str) -> Result<Vec<Line>, String> {
+    input
+        .lines()
+        .filter(|line_str| !line_str.trim().is_empty())
+        .map(|line_str| Line::from_string(line_str.to_string()))
+        .collect()
+}
@@ -0,0 +1,243 @@
+use crate::point::Point;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Slope {
+    Horizontal,
+    Vertical,
+    /// 45°, either direction.
+    Diagonal,
+    /// Anything else.
+    Other,
+}
+
+impl FromStr for Slope {
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Slope, String> {
+        match str {
+            "horizontal" => Ok(Slope::Horizontal),
+            "vertical" => Ok(Slope::Vertical),
+            "diagonal" => Ok(Slope::Diagonal),
+            "other" => Ok(Slope::Other),
+            _ => Err(format!(
+                "Unknown line type '{}', expected horizontal, vertical, diagonal or other",
+                str
+            )),
+        }
+    }
+}
+
+/// Which points of a line get covered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rasterization {
+    /// Only horizontal, vertical and 45° lines, like the puzzle. Other slopes
+    /// are rejected.
+    Puzzle,
+    /// The points Bresenham's algorithm draws, one per step along the major
+    /// axis.
+    Bresenham,
+    /// Only the points with integer coordinates that lie exactly on the line.
+    Lattice,
+}
+impl FromStr for Rasterization {
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Rasterization, String> {
+        match str {
+            "puzzle" => Ok(Rasterization::Puzzle),
+            "bresenham" => Ok(Rasterization::Bresenham),
+            "lattice" => Ok(Rasterization::Lattice),
+            _ => Err(format!(
+                "Unknown mode '{}', expected puzzle, bresenham or lattice",
+                str
+            )),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Line {
+    pub p1: Point,
+    pub p2: Point,
+}
+impl Line {
+    pub fn from_string(str: String) -> Result<Line, String> {
+        let (p1_str, p2_str) = match str.split_once("->") {
+            Some(points) => points,
+            None => return Err(format!("Couldn't parse string to line, str={}", str)),
+        };
+
+        let p1 = Point::from_string(p1_str.to_string())?;
+        let p2 = Point::from_string(p2_str.to_string())?;
+        // Directions are computed as p2 - p1 and their lengths with abs, so
+        // both have to fit in an i64.
+        let fits = p2.checked_sub(p1).is_some_and(|delta| {
+            delta.x.checked_abs().is_some() && delta.y.checked_abs().is_some()
+        });
+        if !fits {
+            return Err(format!(
+                "Line {} -> {} is too long, its ends must be less than 2^63 apart",
+                p1, p2
+            ));
+        }
+
+        Ok(Line { p1, p2 })
+    }
+
+    /// The points exactly on the line. Matches the puzzle for horizontal,
+    /// vertical and 45° lines.
+    pub fn iter(&self) -> LineIterator {
+        LineIterator::lattice(*self)
+    }
+
+    pub fn points(&self, mode: Rasterization) -> Result<LineIterator, String> {
+        match mode {
+            Rasterization::Puzzle if self.slope() == Slope::Other => Err(format!(
+                "Line {} is not horizontal, vertical or diagonal",
+                self
+            )),
+            Rasterization::Puzzle | Rasterization::Lattice => Ok(LineIterator::lattice(*self)),
+            Rasterization::Bresenham => Ok(LineIterator::bresenham(*self)),
+        }
+    }
+
+    pub fn slope(&self) -> Slope {
+        let delta = self.p2 - self.p1;
+        if delta.y == 0 {
+            Slope::Horizontal
+        } else if delta.x == 0 {
+            Slope::Vertical
+        } else if delta.x.abs() == delta.y.abs() {
+            Slope::Diagonal
+        } else {
+            Slope::Other
+        }
+    }
+
+    /// Whether `point` is one of the points `points(mode)` yields.
+    pub fn contains(&self, point: Point, mode: Rasterization) -> bool {
+        let (low_x, high_x) = (self.p1.x.min(self.p2.x), self.p1.x.max(self.p2.x));
+        let (low_y, high_y) = (self.p1.y.min(self.p2.y), self.p1.y.max(self.p2.y));
+        if point.x < low_x || point.x > high_x || point.y < low_y || point.y > high_y {
+            return false;
+        }
+
+        match mode {
+            Rasterization::Puzzle if self.slope() == Slope::Other => false,
+            Rasterization::Puzzle | Rasterization::Lattice => {
+                let direction = self.p2 - self.p1;
+                let offset = point - self.p1;
+                // On the line when the offset is parallel to the direction,
+                // which also puts it on a lattice step within the bounds.
+                direction.x as i128 * offset.y as i128 == direction.y as i128 * offset.x as i128
+            }
+            Rasterization::Bresenham => LineIterator::bresenham(*self).any(|p| p == point),
+        }
+    }
+
+    pub fn is_straight(&self) -> bool {
+        self.p1.x == self.p2.x || self.p1.y == self.p2.y
+    }
+}
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} -> {}", self.p1, self.p2)
+    }
+}
+
+/// Lines in `lines` the puzzle rules don't cover, with their index.
+pub fn unsupported_slopes(lines: &[Line]) -> Vec<(usize, Line)> {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.slope() == Slope::Other)
+        .map(|(i, line)| (i, *line))
+        .collect()
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+pub struct LineIterator {
+    line: Line,
+    delta: Point,
+    current: Point,
+    finished: bool,
+    /// Bresenham error term, unused for lattice stepping.
+    error: Option<i64>,
+}
+impl LineIterator {
+    /// Steps by the direction divided by the gcd of its components, which is
+    /// the smallest step that stays on integer coordinates.
+    fn lattice(line: Line) -> LineIterator {
+        let direction = line.p2 - line.p1;
+        let divisor = gcd(direction.x, direction.y).max(1);
+        LineIterator {
+            delta: Point {
+                x: direction.x / divisor,
+                y: direction.y / divisor,
+            },
+            current: line.p1,
+            line,
+            finished: false,
+            error: None,
+        }
+    }
+
+    fn bresenham(line: Line) -> LineIterator {
+        let direction = line.p2 - line.p1;
+        LineIterator {
+            delta: direction.signum(),
+            current: line.p1,
+            line,
+            finished: false,
+            error: Some(direction.x.abs() - direction.y.abs()),
+        }
+    }
+}
+impl Iterator for LineIterator {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        // Stopping after p2 rather than one step past it also ends single
+        // point lines, whose delta is zero.
+        if self.finished {
+            return None;
+        }
+
+        let to_return = Some(self.current);
+        if self.current == self.line.p2 {
+            self.finished = true;
+            return to_return;
+        }
+
+        match self.error {
+            None => self.current = self.current + self.delta,
+            Some(error) => {
+                let dx = (self.line.p2.x - self.line.p1.x).abs();
+                let dy = (self.line.p2.y - self.line.p1.y).abs();
+                // Twice the error can take more than an i64 on long lines.
+                let doubled = 2 * error as i128;
+                let mut error = error;
+                if doubled > -dy as i128 {
+                    error -= dy;
+                    self.current.x += self.delta.x;
+                }
+                if doubled < dx as i128 {
+                    error += dx;
+                    self.current.y += self.delta.y;
+                }
+                self.error = Some(error);
+            }
+        }
+
+        to_return
+    }
+}
@@ -0,0 +1,67 @@
+use std::fmt;
+use std::ops::{Add, Sub};
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+impl Point {
+    pub fn signum(&self) -> Point {
+        Point {
+            x: self.x.signum(),
+            y: self.y.signum(),
+        }
+    }
+
+    pub fn from_string(str: String) -> Result<Point, String> {
+        let (x_str, y_str) = match str.trim().split_once(',') {
+            Some(s) => s,
+            None => return Err(format!("Couldn't parse to point, str='{}'", str)),
+        };
+
+        let x = match x_str.trim().parse::<i64>() {
+            Ok(x) => x,
+            Err(e) => return Err(format!("{}", e)),
+        };
+        let y = match y_str.trim().parse::<i64>() {
+            Ok(y) => y,
+            Err(e) => return Err(format!("{}", e)),
+        };
+
+        Ok(Point { x, y })
+    }
+
+    /// `self - other`, or `None` if a component doesn't fit in an i64.
+    pub fn checked_sub(&self, other: Point) -> Option<Point> {
+        Some(Point {
+            x: self.x.checked_sub(other.x)?,
+            y: self.y.checked_sub(other.y)?,
+        })
+    }
+}
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
@@ -0,0 +1,83 @@
+use day5::cli::CommonOptions;
+use day5::{LineFilter, Point, Storage};
+use std::env;
+use std::io;
+
+/// Answers questions about how the vent lines cover the map.
+///
+/// Usage: query [--at-least K]... [--histogram] [--hottest N] [--cell X,Y]...
+///              [--lines horizontal,vertical,diagonal,other|straight|all]
+///              [--within X1,Y1,X2,Y2] [--min-length N] [--max-length N]
+///              [--storage dense|sparse|auto] [--mode puzzle|bresenham|lattice]
+///              [--skip-unsupported] [input file]
+fn main() -> io::Result<()> {
+    let mut options = CommonOptions::new(LineFilter::all(), Storage::Auto);
+    let mut thresholds = Vec::new();
+    let mut histogram = false;
+    let mut hottest = None;
+    let mut cells = Vec::new();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--at-least" => thresholds.push(args.next().unwrap().parse::<i32>().unwrap()),
+            "--histogram" => histogram = true,
+            "--hottest" => hottest = Some(args.next().unwrap().parse::<usize>().unwrap()),
+            "--cell" => cells.push(Point::from_string(args.next().unwrap()).unwrap()),
+            _ => {
+                if !options.parse_arg(&arg, &mut args).unwrap() {
+                    panic!("Unknown flag {}", arg);
+                }
+            }
+        }
+    }
+    if let Some(k) = thresholds.iter().find(|k| **k < 1) {
+        panic!(
+            "--at-least counts covered points, K must be at least 1, not {}",
+            k
+        );
+    }
+    if thresholds.is_empty() && !histogram && hottest.is_none() && cells.is_empty() {
+        thresholds.push(2);
+    }
+
+    let lines = match options.read_lines() {
+        Ok(lines) => lines,
+        Err(e) => panic!("{}", e),
+    };
+    let data = match options.vent_data(&lines) {
+        Ok(data) => data,
+        Err(e) => panic!("{}", e),
+    };
+
+    for k in thresholds {
+        println!(
+            "Points covered by at least {} lines={}",
+            k,
+            data.count_at_least(k)
+        );
+    }
+
+    if histogram {
+        println!("Coverage histogram:");
+        for (coverage, count) in data.histogram() {
+            println!("{:>4} lines: {} points", coverage, count);
+        }
+    }
+
+    if let Some(n) = hottest {
+        println!("Hottest points:");
+        for (point, value) in data.hottest(n) {
+            println!("{} covered by {} lines", point, value);
+        }
+    }
+
+    for cell in cells {
+        let through = data.lines_through(cell);
+        println!("{} is covered by {} lines:", cell, through.len());
+        for i in through {
+            println!("  line {}: {}", i + 1, data.lines()[i]);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use crate::grid::{Grid, Storage};
+use crate::line::{Line, Rasterization};
+use crate::point::Point;
+use std::cmp::{max, min, Reverse};
+use std::collections::BTreeMap;
+
+pub struct HydrothermalVentData {
+    grid: Box<dyn Grid>,
+    lines: Vec<Line>,
+    mode: Rasterization,
+    /// Smallest and largest corner of the lines' bounding box.
+    bounds: Option<(Point, Point)>,
+}
+impl HydrothermalVentData {
+    pub fn new(lines: &[Line]) -> HydrothermalVentData {
+        HydrothermalVentData::with_storage(lines, Storage::Dense).unwrap()
+    }
+
+    pub fn with_storage(lines: &[Line], storage: Storage) -> Result<HydrothermalVentData, String> {
+        HydrothermalVentData::with_mode(lines, storage, Rasterization::Puzzle)
+    }
+
+    pub fn with_mode(
+        lines: &[Line],
+        storage: Storage,
+        mode: Rasterization,
+    ) -> Result<HydrothermalVentData, String> {
+        let bounds = bounding_box(lines);
+        let mut grid = match bounds {
+            Some((low, high)) => storage.create(low, high)?,
+            None => storage.create(Point { x: 0, y: 0 }, Point { x: 0, y: 0 })?,
+        };
+
+        for line in lines {
+            for point in line.points(mode)? {
+                let current = match grid.get(point.x, point.y) {
+                    Some(point) => *point,
+                    None => return Err(format!("Couldn't get point {} in line {}", point, line)),
+                };
+                grid.set(point.x, point.y, current + 1)?;
+            }
+        }
+
+        Ok(HydrothermalVentData {
+            grid,
+            lines: lines.to_vec(),
+            mode,
+            bounds,
+        })
+    }
+
+    pub fn grid(&self) -> &dyn Grid {
+        self.grid.as_ref()
+    }
+
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        self.grid.memory_usage()
+    }
+
+    pub fn lines(&self) -> &[Line] {
+        &self.lines
+    }
+
+    pub fn number_of_overlapping_lines(&self) -> i32 {
+        self.count_at_least(2) as i32
+    }
+
+    /// Number of points covered by at least `k` lines. `k` must be positive.
+    pub fn count_at_least(&self, k: i32) -> usize {
+        self.grid.cells().filter(|(_, value)| *value >= k).count()
+    }
+
+    /// How many points are covered by exactly n lines, for every n > 0 that
+    /// occurs.
+    pub fn histogram(&self) -> BTreeMap<i32, usize> {
+        let mut histogram = BTreeMap::new();
+        for (_, value) in self.grid.cells() {
+            *histogram.entry(value).or_insert(0) += 1;
+        }
+        histogram
+    }
+
+    /// The `n` points covered by the most lines, most covered first and ties
+    /// in reading order.
+    pub fn hottest(&self, n: usize) -> Vec<(Point, i32)> {
+        let mut cells = self.grid.cells().collect::<Vec<(Point, i32)>>();
+        cells.sort_by_key(|(point, value)| (Reverse(*value), point.y, point.x));
+        cells.truncate(n);
+        cells
+    }
+
+    /// Indices of the input lines covering `point`.
+    pub fn lines_through(&self, point: Point) -> Vec<usize> {
+        self.lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.contains(point, self.mode))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    pub fn print_map(&self) {
+        let (low, high) = match self.bounds {
+            Some(bounds) => bounds,
+            None => return,
+        };
+        for y in low.y..=high.y {
+            for x in low.x..=high.x {
+                let value = self.grid.get(x, y).unwrap();
+                if *value == 0 {
+                    print!("*");
+                } else {
+                    print!("{}", value);
+                }
+            }
+            println!();
+        }
+    }
+}
+
+fn bounding_box(lines: &[Line]) -> Option<(Point, Point)> {
+    lines.iter().fold(None, |bounds, line| {
+        let low = Point {
+            x: min(line.p1.x, line.p2.x),
+            y: min(line.p1.y, line.p2.y),
+        };
+        let high = Point {
+            x: max(line.p1.x, line.p2.x),
+            y: max(line.p1.y, line.p2.y),
+        };
+        Some(match bounds {
+            Some((current_low, current_high)) => (
+                Point {
+                    x: min(current_low.x, low.x),
+                    y: min(current_low.y, low.y),
+                },
+                Point {
+                    x: max(current_high.x, high.x),
+                    y: max(current_high.y, high.y),
+                },
+            ),
+            None => (low, high),
+        })
+    })
+}
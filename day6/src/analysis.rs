@@ -0,0 +1,146 @@
+use crate::matrix::Matrix;
+use num_bigint::BigUint;
+use std::io;
+use std::io::prelude::*;
+
+/// Fish per timer value for each simulated day, starting with the initial
+/// school on day 0.
+pub struct TimeSeries {
+    counts: Vec<Vec<BigUint>>,
+}
+impl TimeSeries {
+    pub fn new(initial: Vec<BigUint>) -> TimeSeries {
+        TimeSeries {
+            counts: vec![initial],
+        }
+    }
+
+    pub fn push(&mut self, counts: Vec<BigUint>) {
+        self.counts.push(counts);
+    }
+
+    /// Number of days after the initial one.
+    pub fn days(&self) -> usize {
+        self.counts.len() - 1
+    }
+
+    pub fn day(&self, day: usize) -> &[BigUint] {
+        &self.counts[day]
+    }
+
+    pub fn totals(&self) -> Vec<BigUint> {
+        self.counts
+            .iter()
+            .map(|counts| counts.iter().sum())
+            .collect()
+    }
+
+    /// One row per day: the day, the fish with each timer value, and the
+    /// total.
+    pub fn write_csv<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let timers = self.counts[0].len();
+        write!(writer, "day")?;
+        for timer in 0..timers {
+            write!(writer, ",timer_{}", timer)?;
+        }
+        writeln!(writer, ",total")?;
+
+        for (day, counts) in self.counts.iter().enumerate() {
+            write!(writer, "{}", day)?;
+            for count in counts {
+                write!(writer, ",{}", count)?;
+            }
+            writeln!(writer, ",{}", counts.iter().sum::<BigUint>())?;
+        }
+        Ok(())
+    }
+}
+
+/// Asymptotic factor the population grows by each day: the dominant
+/// eigenvalue of `transition`, 0 when the school dies out.
+///
+/// Found by power iteration on `transition + I`, which has the same dominant
+/// eigenvector and converges even when the life cycle makes the transition
+/// periodic, for example when newborns start from the reset timer.
+pub fn growth_rate(transition: &Matrix) -> Result<f64, String> {
+    // Power iteration only creeps towards a dominant eigenvalue of 0, so
+    // that case is found exactly.
+    if dies_out(transition) {
+        return Ok(0.0);
+    }
+
+    let size = transition.size();
+    let mut cells = vec![vec![0.0; size]; size];
+    for (row, cells) in cells.iter_mut().enumerate() {
+        for (column, cell) in cells.iter_mut().enumerate() {
+            let entry = transition.get(row, column);
+            *cell = u64::try_from(entry)
+                .map_err(|_| format!("Transition entry {} doesn't fit in a u64", entry))?
+                as f64;
+            if row == column {
+                *cell += 1.0;
+            }
+        }
+    }
+
+    let mut vector = vec![1.0 / size as f64; size];
+    let mut eigenvalue = 0.0;
+    for _ in 0..100_000 {
+        let next = cells
+            .iter()
+            .map(|row| row.iter().zip(&vector).map(|(a, b)| a * b).sum::<f64>())
+            .collect::<Vec<f64>>();
+        let norm = next.iter().sum::<f64>();
+        if norm == 0.0 {
+            return Ok(0.0);
+        }
+        vector = next.iter().map(|value| value / norm).collect();
+        let converged = (norm - eigenvalue).abs() <= 1e-13 * norm;
+        eigenvalue = norm;
+        if converged {
+            break;
+        }
+    }
+
+    Ok(eigenvalue - 1.0)
+}
+
+/// Whether every fish and all its offspring are gone after at most
+/// `transition.size()` days, whatever the school. With non-negative entries
+/// that is when no state leads back to itself, which makes the dominant
+/// eigenvalue 0.
+fn dies_out(transition: &Matrix) -> bool {
+    let size = transition.size();
+    let leads_to = |from: usize, to: usize| *transition.get(to, from) != BigUint::ZERO;
+    // Removes states nothing left leads to until none are left, which only
+    // happens without a cycle.
+    let mut incoming = (0..size)
+        .map(|to| (0..size).filter(|from| leads_to(*from, to)).count())
+        .collect::<Vec<usize>>();
+    let mut removable = (0..size)
+        .filter(|to| incoming[*to] == 0)
+        .collect::<Vec<usize>>();
+    let mut removed = 0;
+    while let Some(from) = removable.pop() {
+        removed += 1;
+        for (to, incoming) in incoming.iter_mut().enumerate() {
+            if leads_to(from, to) {
+                *incoming -= 1;
+                if *incoming == 0 {
+                    removable.push(to);
+                }
+            }
+        }
+    }
+    removed == size
+}
+
+/// Days for the population to double at `rate`, or `None` if it doesn't
+/// grow.
+pub fn doubling_time(rate: f64) -> Option<f64> {
+    if rate > 1.0 {
+        Some(2f64.ln() / rate.ln())
+    } else {
+        None
+    }
+}
@@ -0,0 +1,124 @@
+use std::io;
+use std::io::prelude::*;
+
+/// Largest family tree `write_dot` agrees to export.
+pub const MAX_DOT_FISH: usize = 5_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Record {
+    /// `None` for the initial fish.
+    pub parent: Option<usize>,
+    /// Day the fish was born on, 0 for the initial fish.
+    pub birth_day: usize,
+    /// 0 for the initial fish, 1 for their children, and so on.
+    pub generation: usize,
+}
+
+/// Every fish that ever lived, dead or alive, indexed by id. The initial
+/// fish come first, and children always come after their parent.
+pub struct Genealogy {
+    records: Vec<Record>,
+    founders: usize,
+}
+impl Genealogy {
+    /// Records `founders` initial fish, with ids 0 to `founders - 1`.
+    pub fn new(founders: usize) -> Genealogy {
+        Genealogy {
+            records: vec![
+                Record {
+                    parent: None,
+                    birth_day: 0,
+                    generation: 0,
+                };
+                founders
+            ],
+            founders,
+        }
+    }
+
+    /// Records a child of `parent` born on `day`, and returns its id.
+    pub fn add_child(&mut self, parent: usize, day: usize) -> usize {
+        let generation = self.records[parent].generation + 1;
+        self.records.push(Record {
+            parent: Some(parent),
+            birth_day: day,
+            generation,
+        });
+        self.records.len() - 1
+    }
+
+    pub fn record(&self, id: usize) -> &Record {
+        &self.records[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Initial fish each fish descends from, by id.
+    fn founder_of(&self) -> Vec<usize> {
+        let mut founder_of = Vec::with_capacity(self.records.len());
+        for (id, record) in self.records.iter().enumerate() {
+            let founder = match record.parent {
+                Some(parent) => founder_of[parent],
+                None => id,
+            };
+            founder_of.push(founder);
+        }
+        founder_of
+    }
+
+    /// Number of descendants, over all generations and counting those that
+    /// have died, of each initial fish.
+    pub fn descendants_per_founder(&self) -> Vec<usize> {
+        let mut descendants = vec![0; self.founders];
+        for (id, founder) in self.founder_of().into_iter().enumerate() {
+            if id != founder {
+                descendants[founder] += 1;
+            }
+        }
+        descendants
+    }
+
+    pub fn deepest_generation(&self) -> usize {
+        self.records
+            .iter()
+            .map(|record| record.generation)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The family tree as a Graphviz digraph, with an edge from each parent
+    /// to each child. Fish are labelled with their id plus one, to match
+    /// the numbering of the initial fish, and their birth day.
+    pub fn write_dot<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        if self.records.len() > MAX_DOT_FISH {
+            return Err(io::Error::other(format!(
+                "{} fish are too many to draw, simulate fewer days",
+                self.records.len()
+            )));
+        }
+
+        writeln!(writer, "digraph lanternfish {{")?;
+        writeln!(writer, "    node [shape=box];")?;
+        for (id, record) in self.records.iter().enumerate() {
+            writeln!(
+                writer,
+                "    f{} [label=\"#{}\\nborn day {}\"];",
+                id,
+                id + 1,
+                record.birth_day
+            )?;
+        }
+        for (id, record) in self.records.iter().enumerate() {
+            if let Some(parent) = record.parent {
+                writeln!(writer, "    f{} -> f{};", parent, id)?;
+            }
+        }
+        writeln!(writer, "}}")
+    }
+}
@@ -0,0 +1,86 @@
+use day6::analysis::{doubling_time, growth_rate};
+use day6::{Lifecycle, School};
+use num_bigint::BigUint;
+use std::env;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+
+/// Simulates the school and reports how fast it grows.
+///
+/// Usage: growth [--days N] [--csv FILE] [--threshold T] [--max-days N]
+///               [--reset N] [--newborn N] [--litter N] [--lifespan N]
+///               [input file]
+///
+/// `--csv` writes the fish per timer value for each of the `--days` days,
+/// 256 by default. `--threshold` looks for the first day the population is
+/// above T, searching up to `--max-days`, 100000 by default.
+fn main() -> io::Result<()> {
+    let mut days = 256;
+    let mut csv = None;
+    let mut threshold = None;
+    let mut max_days = 100_000;
+    let mut lifecycle = Lifecycle::default();
+    let mut path = "./input.txt".to_string();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--days" => days = args.next().unwrap().parse::<usize>().unwrap(),
+            "--csv" => csv = Some(args.next().unwrap()),
+            "--threshold" => threshold = Some(args.next().unwrap().parse::<BigUint>().unwrap()),
+            "--max-days" => max_days = args.next().unwrap().parse::<u64>().unwrap(),
+            _ => {
+                if !lifecycle.parse_arg(&arg, &mut args).unwrap() {
+                    path = arg;
+                }
+            }
+        }
+    }
+
+    let file = fs::File::open(path)?;
+    let mut reader = io::BufReader::new(file);
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+
+    let school = match School::with_lifecycle(input, lifecycle) {
+        Ok(school) => school,
+        Err(e) => panic!("{}", e),
+    };
+
+    let rate = match growth_rate(&school.transition()) {
+        Ok(rate) => rate,
+        Err(e) => panic!("{}", e),
+    };
+    if rate == 0.0 {
+        println!("Growth rate=0 per day, no growth, the school dies out");
+    } else {
+        println!("Growth rate={:.9} per day", rate);
+    }
+    match doubling_time(rate) {
+        Some(time) => println!("Doubling time={:.3} days", time),
+        None => println!("Doubling time=never, the school doesn't grow"),
+    }
+
+    if let Some(threshold) = threshold {
+        match school.first_day_above(&threshold, max_days) {
+            Some(day) => println!("Above {} fishes on day {}", threshold, day),
+            None => println!("Not above {} fishes by day {}", threshold, max_days),
+        }
+    }
+
+    let series = school.simulate(days);
+    println!(
+        "After day {}, n fishes={}",
+        series.days(),
+        series.totals()[series.days()]
+    );
+    if let Some(csv) = csv {
+        let file = fs::File::create(&csv)?;
+        let mut writer = io::BufWriter::new(file);
+        series.write_csv(&mut writer)?;
+        writer.flush()?;
+        println!("Wrote {} days to {}", series.days(), csv);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use day6::inference::{infer, parse_observations};
+use day6::Lifecycle;
+use std::env;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+
+/// Works out which initial schools lead to a sequence of population totals.
+///
+/// Usage: infer [--first-day N] [--limit N] [--reset N] [--newborn N]
+///              [--litter N] [--lifespan N] [observations file]
+///
+/// Reads the totals from standard input unless a file is given, so the
+/// output of part1 or part2 can be piped in. Bare numbers are taken to be
+/// consecutive days from `--first-day`, 1 by default. At most `--limit`
+/// schools are printed, 20 by default.
+fn main() -> io::Result<()> {
+    let mut first_day = 1;
+    let mut limit = 20;
+    let mut lifecycle = Lifecycle::default();
+    let mut path = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--first-day" => first_day = args.next().unwrap().parse::<u64>().unwrap(),
+            "--limit" => limit = args.next().unwrap().parse::<usize>().unwrap(),
+            _ => {
+                if !lifecycle.parse_arg(&arg, &mut args).unwrap() {
+                    path = Some(arg);
+                }
+            }
+        }
+    }
+
+    let mut input = String::new();
+    match path {
+        Some(path) => {
+            let file = fs::File::open(path)?;
+            let mut reader = io::BufReader::new(file);
+            reader.read_to_string(&mut input)?;
+        }
+        None => {
+            io::stdin().read_to_string(&mut input)?;
+        }
+    }
+
+    let observations = match parse_observations(&input, first_day) {
+        Ok(observations) => observations,
+        Err(e) => panic!("{}", e),
+    };
+    let inference = match infer(&lifecycle, &observations, limit) {
+        Ok(inference) => inference,
+        Err(e) => panic!("{}", e),
+    };
+
+    for school in &inference.schools {
+        let counts = school
+            .iter()
+            .map(|count| count.to_string())
+            .collect::<Vec<String>>()
+            .join(",");
+        println!("Fish per timer={}", counts);
+    }
+    if inference.complete {
+        println!("{} initial schools match", inference.schools.len());
+    } else {
+        println!(
+            "At least {} initial schools match, stopped looking",
+            inference.schools.len()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,263 @@
+use crate::school::{Lifecycle, School};
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+
+/// Most candidate schools `infer` tries before giving up.
+pub const NODE_LIMIT: u64 = 10_000_000;
+
+pub struct Inference {
+    /// Fish per timer value of each initial school that leads to the
+    /// observed totals.
+    pub schools: Vec<Vec<u64>>,
+    /// Whether `schools` holds every such school. It doesn't when the limit
+    /// on schools or `NODE_LIMIT` was hit.
+    pub complete: bool,
+}
+
+/// Parses population totals, one per line, either as the simulators print
+/// them (`After day 3, n fishes=420`) or as bare numbers on consecutive days
+/// from `first_day`.
+pub fn parse_observations(str: &str, first_day: u64) -> Result<Vec<(u64, BigUint)>, String> {
+    let mut observations = Vec::new();
+    let mut next_day = first_day;
+    for line in str
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+    {
+        let (day, total) = match line.rsplit_once('=') {
+            Some((label, total)) => {
+                let day = label
+                    .trim_start_matches("After day")
+                    .trim_start()
+                    .split(|c: char| !c.is_ascii_digit())
+                    .next()
+                    .and_then(|day| day.parse::<u64>().ok())
+                    .ok_or(format!("Couldn't find the day in '{}'", line))?;
+                (day, total)
+            }
+            None => (next_day, line),
+        };
+        let total = total
+            .trim()
+            .parse::<BigUint>()
+            .map_err(|e| format!("Couldn't parse total in '{}' due to {}", line, e))?;
+        observations.push((day, total));
+        next_day = day + 1;
+    }
+    if observations.is_empty() {
+        return Err("There are no observations".to_string());
+    }
+    Ok(observations)
+}
+
+/// Finds the initial schools, as fish per timer value with every fish
+/// newborn, whose population is `total` on `day` for every observation. At
+/// most `limit` schools are returned.
+///
+/// Each total is a linear function of the initial counts, with coefficients
+/// from ticking a single fish of each timer value, so the observations form
+/// a linear system. Gaussian elimination leaves the counts of some timers
+/// free; those are enumerated within the bounds the totals allow, and the
+/// rest are solved for and kept when they are whole and not negative. With
+/// totals for a dozen or so days the solution is usually unique.
+pub fn infer(
+    lifecycle: &Lifecycle,
+    observations: &[(u64, BigUint)],
+    limit: usize,
+) -> Result<Inference, String> {
+    let timers = lifecycle.max_timer() + 1;
+    let last_day = observations.iter().map(|(day, _)| *day).max().unwrap_or(0);
+
+    // coefficients[timer][day] is the population on `day` grown from one
+    // fish with that timer.
+    let mut coefficients = Vec::with_capacity(timers);
+    for timer in 0..timers {
+        let mut counts = vec![0; timers];
+        counts[timer] = 1;
+        coefficients.push(School::from_counts(&counts, *lifecycle)?.totals(last_day));
+    }
+
+    let mut bounds = Vec::with_capacity(timers);
+    for (timer, of_timer) in coefficients.iter().enumerate() {
+        let bound = observations
+            .iter()
+            .filter(|(day, _)| of_timer[*day as usize] != BigUint::default())
+            .map(|(day, total)| total / &of_timer[*day as usize])
+            .min()
+            .ok_or(format!(
+                "Fish with timer {} don't change any observed total",
+                timer
+            ))?;
+        bounds.push(u64::try_from(bound).unwrap_or(u64::MAX));
+    }
+
+    let rows = observations
+        .iter()
+        .map(|(day, total)| {
+            let mut row = coefficients
+                .iter()
+                .map(|of_timer| BigInt::from(of_timer[*day as usize].clone()))
+                .collect::<Vec<BigInt>>();
+            row.push(BigInt::from(total.clone()));
+            row
+        })
+        .collect::<Vec<Vec<BigInt>>>();
+
+    let mut search = Search {
+        rows: rows.clone(),
+        pivots: Vec::new(),
+        free: Vec::new(),
+        bounds,
+        observations: rows,
+        values: vec![0; timers],
+        schools: Vec::new(),
+        limit,
+        nodes: 0,
+    };
+    if !search.eliminate() {
+        return Ok(Inference {
+            schools: Vec::new(),
+            complete: true,
+        });
+    }
+
+    let residuals = search
+        .observations
+        .iter()
+        .map(|row| row[timers].clone())
+        .collect::<Vec<BigInt>>();
+    search.enumerate(0, &residuals);
+
+    let complete = search.schools.len() <= limit && search.nodes < NODE_LIMIT;
+    search.schools.truncate(limit);
+    Ok(Inference {
+        schools: search.schools,
+        complete,
+    })
+}
+
+struct Search {
+    /// The system in reduced row echelon form, each row ending with its
+    /// total.
+    rows: Vec<Vec<BigInt>>,
+    /// Column of the pivot of each row.
+    pivots: Vec<usize>,
+    /// Columns without a pivot.
+    free: Vec<usize>,
+    bounds: Vec<u64>,
+    /// The system as given, for pruning.
+    observations: Vec<Vec<BigInt>>,
+    values: Vec<u64>,
+    schools: Vec<Vec<u64>>,
+    limit: usize,
+    nodes: u64,
+}
+impl Search {
+    /// Brings `rows` to reduced row echelon form without fractions. Returns
+    /// `false` if the system has no solution at all.
+    fn eliminate(&mut self) -> bool {
+        let columns = self.values.len();
+        let mut next = 0;
+        for column in 0..columns {
+            let pivot = match (next..self.rows.len())
+                .find(|&i| self.rows[i][column].sign() != Sign::NoSign)
+            {
+                Some(pivot) => pivot,
+                None => {
+                    self.free.push(column);
+                    continue;
+                }
+            };
+            self.rows.swap(next, pivot);
+            let pivot_row = self.rows[next].clone();
+            for (i, row) in self.rows.iter_mut().enumerate() {
+                if i == next || row[column].sign() == Sign::NoSign {
+                    continue;
+                }
+                let factor = row[column].clone();
+                for (value, pivot_value) in row.iter_mut().zip(&pivot_row) {
+                    *value = &*value * &pivot_row[column] - pivot_value * &factor;
+                }
+                reduce(row);
+            }
+            self.pivots.push(column);
+            next += 1;
+        }
+
+        // Rows past the pivots have no coefficients left, so their totals
+        // must be zero too.
+        let consistent = self.rows[next..]
+            .iter()
+            .all(|row| row[columns].sign() == Sign::NoSign);
+        self.rows.truncate(next);
+        consistent
+    }
+
+    /// Tries every value of the free column `i` onwards. `residuals` is what
+    /// is left of each observed total after the values chosen so far.
+    fn enumerate(&mut self, i: usize, residuals: &[BigInt]) {
+        if self.schools.len() > self.limit || self.nodes >= NODE_LIMIT {
+            return;
+        }
+        self.nodes += 1;
+
+        if i == self.free.len() {
+            if self.solve_pivots() {
+                self.schools.push(self.values.clone());
+            }
+            return;
+        }
+
+        let column = self.free[i];
+        for value in 0..=self.bounds[column] {
+            let next = residuals
+                .iter()
+                .zip(&self.observations)
+                .map(|(residual, row)| residual - &row[column] * value)
+                .collect::<Vec<BigInt>>();
+            // Every coefficient is non-negative, so once a total is
+            // overshot larger values only overshoot it further.
+            if next.iter().any(|residual| residual.sign() == Sign::Minus) {
+                break;
+            }
+            self.values[column] = value;
+            self.enumerate(i + 1, &next);
+            if self.schools.len() > self.limit || self.nodes >= NODE_LIMIT {
+                break;
+            }
+        }
+        self.values[column] = 0;
+    }
+
+    /// Fills in the pivot columns from the free ones. Returns `false` unless
+    /// they all come out as whole, non-negative counts.
+    fn solve_pivots(&mut self) -> bool {
+        let columns = self.values.len();
+        for (row, &pivot) in self.rows.iter().zip(&self.pivots) {
+            let mut numerator = row[columns].clone();
+            for &column in &self.free {
+                numerator -= &row[column] * self.values[column];
+            }
+            let (value, remainder) = numerator.div_rem(&row[pivot]);
+            if remainder.sign() != Sign::NoSign {
+                return false;
+            }
+            match u64::try_from(&value) {
+                Ok(value) => self.values[pivot] = value,
+                Err(_) => return false,
+            }
+        }
+        true
+    }
+}
+
+/// Divides a row by the greatest common divisor of its entries.
+fn reduce(row: &mut [BigInt]) {
+    let divisor = row
+        .iter()
+        .fold(BigInt::default(), |divisor, value| divisor.gcd(value));
+    if divisor.sign() != Sign::NoSign && divisor != BigInt::from(1) {
+        row.iter_mut().for_each(|value| *value /= &divisor);
+    }
+}
@@ -0,0 +1,10 @@
+pub mod analysis;
+pub mod genealogy;
+pub mod inference;
+pub mod matrix;
+pub mod school;
+
+pub use analysis::TimeSeries;
+pub use genealogy::Genealogy;
+pub use matrix::Matrix;
+pub use school::{Lifecycle, School};
@@ -0,0 +1,96 @@
+use num_bigint::BigUint;
+
+/// A square matrix of arbitrary precision counts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Matrix {
+    size: usize,
+    cells: Vec<BigUint>,
+}
+impl Matrix {
+    pub fn zero(size: usize) -> Matrix {
+        Matrix {
+            size,
+            cells: vec![BigUint::default(); size * size],
+        }
+    }
+
+    pub fn identity(size: usize) -> Matrix {
+        let mut matrix = Matrix::zero(size);
+        for i in 0..size {
+            matrix.set(i, i, 1u32.into());
+        }
+        matrix
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> &BigUint {
+        &self.cells[row * self.size + column]
+    }
+
+    pub fn set(&mut self, row: usize, column: usize, value: BigUint) {
+        self.cells[row * self.size + column] = value;
+    }
+
+    /// `self * other`, with every entry reduced by `modulus` if given.
+    pub fn multiply(&self, other: &Matrix, modulus: Option<&BigUint>) -> Matrix {
+        assert_eq!(self.size, other.size, "Matrix sizes differ");
+        let mut product = Matrix::zero(self.size);
+        for row in 0..self.size {
+            for k in 0..self.size {
+                let a = self.get(row, k);
+                if *a == BigUint::default() {
+                    continue;
+                }
+                for column in 0..self.size {
+                    let b = other.get(k, column);
+                    if *b == BigUint::default() {
+                        continue;
+                    }
+                    product.cells[row * self.size + column] += a * b;
+                }
+            }
+        }
+        if let Some(modulus) = modulus {
+            product.cells.iter_mut().for_each(|cell| *cell %= modulus);
+        }
+        product
+    }
+
+    /// `self` to the power `n` by repeated squaring.
+    pub fn pow(&self, mut n: u64, modulus: Option<&BigUint>) -> Matrix {
+        let mut result = Matrix::identity(self.size);
+        let mut square = self.clone();
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result.multiply(&square, modulus);
+            }
+            n >>= 1;
+            if n > 0 {
+                square = square.multiply(&square, modulus);
+            }
+        }
+        if let Some(modulus) = modulus {
+            result.cells.iter_mut().for_each(|cell| *cell %= modulus);
+        }
+        result
+    }
+
+    /// `self * vector`, with every entry reduced by `modulus` if given.
+    pub fn apply(&self, vector: &[BigUint], modulus: Option<&BigUint>) -> Vec<BigUint> {
+        assert_eq!(self.size, vector.len(), "Vector size differs");
+        (0..self.size)
+            .map(|row| {
+                let value = (0..self.size)
+                    .map(|k| self.get(row, k) * &vector[k])
+                    .sum::<BigUint>();
+                match modulus {
+                    Some(modulus) => value % modulus,
+                    None => value,
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,56 @@
+use day6::{Lifecycle, School};
+use num_bigint::BigUint;
+use std::env;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+
+/// Population after any number of days, by matrix exponentiation.
+///
+/// Usage: matrix [--days N] [--modulus M] [--reset N] [--newborn N]
+///               [--litter N] [--lifespan N] [input file]
+///
+/// `--days` defaults to 256. Without `--modulus` the exact population is
+/// printed, which has about N / 26 digits with the puzzle's life cycle. A
+/// lifespan multiplies the size of the transition matrix by that many days.
+fn main() -> io::Result<()> {
+    let mut days = 256;
+    let mut modulus = None;
+    let mut lifecycle = Lifecycle::default();
+    let mut path = "./input.txt".to_string();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--days" => days = args.next().unwrap().parse::<u64>().unwrap(),
+            "--modulus" => modulus = Some(args.next().unwrap().parse::<BigUint>().unwrap()),
+            _ => {
+                if !lifecycle.parse_arg(&arg, &mut args).unwrap() {
+                    path = arg;
+                }
+            }
+        }
+    }
+    if modulus == Some(BigUint::default()) {
+        panic!("The modulus must be positive");
+    }
+
+    let file = fs::File::open(path)?;
+    let mut reader = io::BufReader::new(file);
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+
+    let school = match School::with_lifecycle(input, lifecycle) {
+        Ok(school) => school,
+        Err(e) => panic!("{}", e),
+    };
+    let population = school.population_after(days, modulus.as_ref());
+    match modulus {
+        Some(modulus) => println!(
+            "After day {}, n fishes mod {}={}",
+            days, modulus, population
+        ),
+        None => println!("After day {}, n fishes={}", days, population),
+    }
+
+    Ok(())
+}
@@ -1,54 +1,108 @@
-use std::fmt;
+use day6::{Genealogy, Lifecycle};
+use std::env;
 use std::fs;
 use std::io;
 use std::io::prelude::*;
-use std::ops::{Add, Sub};
-
-const MAX_DAYS: i32 = 6;
-const SPAWN_AGE: i32 = 8;
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 struct Fish {
-    age: i32,
+    age: usize,
+    days_lived: usize,
+    /// Id in the genealogy, when tracking.
+    id: Option<usize>,
 }
 impl Fish {
-    fn tick(&mut self) -> bool {
-        if self.age == 0 {
-            self.age = MAX_DAYS;
+    /// Advances one day. Returns whether the fish spawns and whether it is
+    /// still alive afterwards.
+    fn tick(&mut self, lifecycle: &Lifecycle) -> (bool, bool) {
+        let spawns = if self.age == 0 {
+            self.age = lifecycle.reset_timer;
             true
         } else {
-            self.age = self.age - 1;
+            self.age -= 1;
             false
-        }
+        };
+        self.days_lived += 1;
+        let alive = lifecycle
+            .lifespan
+            .is_none_or(|lifespan| self.days_lived < lifespan);
+        (spawns, alive)
     }
 }
 struct School {
+    lifecycle: Lifecycle,
     fishes: Vec<Fish>,
+    day: usize,
+    /// Parent and birth day of every fish, when tracking.
+    genealogy: Option<Genealogy>,
 }
 impl School {
-    fn from_string(str: String) -> Result<School, String> {
-        let ages: Vec<i32> = match str
+    fn from_string(str: String, lifecycle: Lifecycle, track: bool) -> Result<School, String> {
+        lifecycle.validate()?;
+        let ages: Vec<usize> = match str
             .trim()
-            .split(",")
-            .map(|age_str| age_str.parse::<i32>())
+            .split(',')
+            .map(|age_str| age_str.trim().parse::<usize>())
             .collect()
         {
             Ok(ages) => ages,
             Err(e) => return Err(format!("Couldn't parse school due to {}", e)),
         };
+        if let Some(age) = ages.iter().find(|age| **age > lifecycle.max_timer()) {
+            return Err(format!(
+                "Couldn't parse school, timer {} is above {}",
+                age,
+                lifecycle.max_timer()
+            ));
+        }
 
-        let fishes: Vec<Fish> = ages.iter().cloned().map(|age| Fish { age }).collect();
+        let fishes: Vec<Fish> = ages
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(i, age)| Fish {
+                age,
+                days_lived: 0,
+                id: if track { Some(i) } else { None },
+            })
+            .collect();
+        let genealogy = if track {
+            Some(Genealogy::new(fishes.len()))
+        } else {
+            None
+        };
 
-        Ok(School { fishes })
+        Ok(School {
+            lifecycle,
+            fishes,
+            day: 0,
+            genealogy,
+        })
     }
 
     fn tick(&mut self) {
+        self.day += 1;
         let mut new_fishes = Vec::new();
-        for fish in self.fishes.iter_mut() {
-            if fish.tick() {
-                new_fishes.push(Fish { age: SPAWN_AGE });
+        let lifecycle = self.lifecycle;
+        let day = self.day;
+        let genealogy = &mut self.genealogy;
+        self.fishes.retain_mut(|fish| {
+            let (spawns, alive) = fish.tick(&lifecycle);
+            if spawns {
+                for _ in 0..lifecycle.litter_size {
+                    let id = match (genealogy.as_mut(), fish.id) {
+                        (Some(genealogy), Some(parent)) => Some(genealogy.add_child(parent, day)),
+                        _ => None,
+                    };
+                    new_fishes.push(Fish {
+                        age: lifecycle.newborn_timer,
+                        days_lived: 0,
+                        id,
+                    });
+                }
             }
-        }
+            alive
+        });
 
         self.fishes.append(&mut new_fishes);
     }
@@ -58,20 +112,64 @@ impl School {
     }
 }
 
+/// Usage: part1 [--days N] [--track] [--dot FILE] [--reset N] [--newborn N]
+///              [--litter N] [--lifespan N] [input file]
+///
+/// `--track` records the parent and birth day of every fish, and reports
+/// the descendants of each initial fish and the deepest generation.
+/// `--dot` also writes the family tree in Graphviz format, for small
+/// numbers of days.
 fn main() -> io::Result<()> {
-    let file = fs::File::open("./input.txt")?;
+    let mut days = 80;
+    let mut track = false;
+    let mut dot = None;
+    let mut lifecycle = Lifecycle::default();
+    let mut path = "./input.txt".to_string();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--days" => days = args.next().unwrap().parse::<usize>().unwrap(),
+            "--track" => track = true,
+            "--dot" => {
+                track = true;
+                dot = Some(args.next().unwrap());
+            }
+            _ => {
+                if !lifecycle.parse_arg(&arg, &mut args).unwrap() {
+                    path = arg;
+                }
+            }
+        }
+    }
+
+    let file = fs::File::open(path)?;
     let mut reader = io::BufReader::new(file);
     let mut input = String::new();
     reader.read_to_string(&mut input)?;
 
-    let mut school = match School::from_string(input) {
+    let mut school = match School::from_string(input, lifecycle, track) {
         Ok(school) => school,
         Err(e) => panic!("{}", e),
     };
-    for i in 1..=80 {
+    for i in 1..=days {
         school.tick();
         println!("After day {} fishes={}", i, school.len());
     }
 
+    if let Some(genealogy) = &school.genealogy {
+        for (i, descendants) in genealogy.descendants_per_founder().iter().enumerate() {
+            println!("Fish {} has {} descendants", i + 1, descendants);
+        }
+        println!("Deepest generation={}", genealogy.deepest_generation());
+
+        if let Some(dot) = dot {
+            let file = fs::File::create(&dot)?;
+            let mut writer = io::BufWriter::new(file);
+            genealogy.write_dot(&mut writer)?;
+            writer.flush()?;
+            println!("Wrote family tree of {} fishes to {}", genealogy.len(), dot);
+        }
+    }
+
     Ok(())
 }
@@ -0,0 +1,287 @@
+use crate::analysis::TimeSeries;
+use crate::matrix::Matrix;
+use num_bigint::BigUint;
+
+/// How lanternfish age and spawn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Lifecycle {
+    /// Timer a fish restarts from after spawning.
+    pub reset_timer: usize,
+    /// Timer a newborn fish starts from.
+    pub newborn_timer: usize,
+    /// Fish born each time a fish spawns.
+    pub litter_size: u64,
+    /// Days a fish lives, counted from its birth or, for the initial fish,
+    /// from the first day. It still spawns on its last day. `None` lives
+    /// forever.
+    pub lifespan: Option<usize>,
+}
+impl Lifecycle {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.lifespan == Some(0) {
+            return Err("The lifespan must be at least one day".to_string());
+        }
+        Ok(())
+    }
+
+    /// Highest timer a fish can have.
+    pub fn max_timer(&self) -> usize {
+        self.reset_timer.max(self.newborn_timer)
+    }
+
+    /// Number of ages that need telling apart, 1 when fish live forever.
+    fn ages(&self) -> usize {
+        self.lifespan.unwrap_or(1)
+    }
+
+    /// Handles `arg` if it is a life-cycle flag, taking its value from
+    /// `args`. Returns `false` for anything else.
+    pub fn parse_arg<I: Iterator<Item = String>>(
+        &mut self,
+        arg: &str,
+        args: &mut I,
+    ) -> Result<bool, String> {
+        let mut value = || args.next().ok_or(format!("{} needs a value", arg));
+        match arg {
+            "--reset" => self.reset_timer = value()?.parse().map_err(|e| format!("{}", e))?,
+            "--newborn" => self.newborn_timer = value()?.parse().map_err(|e| format!("{}", e))?,
+            "--litter" => self.litter_size = value()?.parse().map_err(|e| format!("{}", e))?,
+            "--lifespan" => self.lifespan = Some(value()?.parse().map_err(|e| format!("{}", e))?),
+            _ => return Ok(false),
+        }
+        self.validate()?;
+        Ok(true)
+    }
+}
+impl Default for Lifecycle {
+    /// The puzzle's: restart from 6, newborns start from 8, one child each
+    /// time, and no deaths.
+    fn default() -> Self {
+        Lifecycle {
+            reset_timer: 6,
+            newborn_timer: 8,
+            litter_size: 1,
+            lifespan: None,
+        }
+    }
+}
+
+/// Number of fish in each state. A state is a timer value and, when fish
+/// have a lifespan, the number of days the fish has lived.
+pub struct School {
+    lifecycle: Lifecycle,
+    fishes: Vec<u64>,
+}
+impl School {
+    pub fn from_string(str: String) -> Result<School, String> {
+        School::with_lifecycle(str, Lifecycle::default())
+    }
+
+    pub fn with_lifecycle(str: String, lifecycle: Lifecycle) -> Result<School, String> {
+        let timers: Vec<usize> = match str
+            .trim()
+            .split(',')
+            .map(|timer_str| timer_str.trim().parse::<usize>())
+            .collect()
+        {
+            Ok(timers) => timers,
+            Err(e) => return Err(format!("Couldn't parse school due to {}", e)),
+        };
+
+        let mut counts = vec![0; lifecycle.max_timer() + 1];
+        for timer in timers {
+            if timer > lifecycle.max_timer() {
+                return Err(format!(
+                    "Couldn't parse school, timer {} is above {}",
+                    timer,
+                    lifecycle.max_timer()
+                ));
+            }
+            counts[timer] += 1;
+        }
+
+        School::from_counts(&counts, lifecycle)
+    }
+
+    /// A school with `counts[timer]` fish of each timer value, all newborn
+    /// as far as their lifespan goes.
+    pub fn from_counts(counts: &[u64], lifecycle: Lifecycle) -> Result<School, String> {
+        lifecycle.validate()?;
+        if counts.len() != lifecycle.max_timer() + 1 {
+            return Err(format!(
+                "Expected counts for timers 0 to {}, got {}",
+                lifecycle.max_timer(),
+                counts.len()
+            ));
+        }
+
+        let mut school = School {
+            lifecycle,
+            fishes: vec![0; counts.len() * lifecycle.ages()],
+        };
+        for (timer, count) in counts.iter().enumerate() {
+            let state = school.state(timer, 0);
+            school.fishes[state] = *count;
+        }
+
+        Ok(school)
+    }
+
+    pub fn lifecycle(&self) -> &Lifecycle {
+        &self.lifecycle
+    }
+
+    fn state(&self, timer: usize, age: usize) -> usize {
+        timer * self.lifecycle.ages() + age
+    }
+
+    /// States a fish in `state` leads to the next day, with how many fish
+    /// end up in each.
+    fn successors(&self, state: usize) -> Vec<(usize, u64)> {
+        let ages = self.lifecycle.ages();
+        let (timer, age) = (state / ages, state % ages);
+        let mut successors = Vec::with_capacity(2);
+        let next_age = match self.lifecycle.lifespan {
+            Some(lifespan) if age + 1 >= lifespan => None,
+            Some(_) => Some(age + 1),
+            None => Some(0),
+        };
+        if timer == 0 {
+            if self.lifecycle.litter_size > 0 {
+                successors.push((
+                    self.state(self.lifecycle.newborn_timer, 0),
+                    self.lifecycle.litter_size,
+                ));
+            }
+            if let Some(next_age) = next_age {
+                successors.push((self.state(self.lifecycle.reset_timer, next_age), 1));
+            }
+        } else if let Some(next_age) = next_age {
+            successors.push((self.state(timer - 1, next_age), 1));
+        }
+        successors
+    }
+
+    /// Number of fish with each timer value, from 0 to the highest timer.
+    pub fn counts(&self) -> Vec<u64> {
+        self.fishes
+            .chunks(self.lifecycle.ages())
+            .map(|of_timer| of_timer.iter().sum())
+            .collect()
+    }
+
+    /// Advances one day. Panics once a count no longer fits in a `u64`,
+    /// which happens a little after day 450 for the puzzle input; use
+    /// `population_after` beyond that.
+    pub fn tick(&mut self) {
+        let mut next = vec![0u64; self.fishes.len()];
+        for (state, &n) in self.fishes.iter().enumerate() {
+            if n == 0 {
+                continue;
+            }
+            for (successor, weight) in self.successors(state) {
+                next[successor] = n
+                    .checked_mul(weight)
+                    .and_then(|born| next[successor].checked_add(born))
+                    .expect("Fish count overflowed a u64");
+            }
+        }
+        self.fishes = next;
+    }
+
+    /// Fish per timer value on every day from 0 to `days`, counted exactly.
+    /// The school itself isn't advanced.
+    pub fn simulate(&self, days: usize) -> TimeSeries {
+        let transition = self.transition();
+        let mut fishes = self.big_counts();
+        let mut series = TimeSeries::new(self.per_timer(&fishes));
+        for _ in 0..days {
+            fishes = transition.apply(&fishes, None);
+            series.push(self.per_timer(&fishes));
+        }
+        series
+    }
+
+    pub fn len(&self) -> u64 {
+        self.fishes
+            .iter()
+            .try_fold(0u64, |count, n_of_age| count.checked_add(*n_of_age))
+            .expect("Fish count overflowed a u64")
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fishes.iter().all(|n_of_age| *n_of_age == 0)
+    }
+
+    /// Sums `fishes`, a count per state, over the ages of each timer value.
+    fn per_timer(&self, fishes: &[BigUint]) -> Vec<BigUint> {
+        fishes
+            .chunks(self.lifecycle.ages())
+            .map(|of_timer| of_timer.iter().sum())
+            .collect()
+    }
+
+    fn big_counts(&self) -> Vec<BigUint> {
+        self.fishes.iter().map(|n| BigUint::from(*n)).collect()
+    }
+
+    /// The matrix taking the counts of one day to the next: entry (i, j) is
+    /// how many fish in state i a fish in state j turns into.
+    ///
+    /// Its size is the number of timer values times the lifespan, so long
+    /// lifespans make it slow.
+    pub fn transition(&self) -> Matrix {
+        let mut matrix = Matrix::zero(self.fishes.len());
+        for state in 0..self.fishes.len() {
+            for (successor, weight) in self.successors(state) {
+                let value = matrix.get(successor, state) + BigUint::from(weight);
+                matrix.set(successor, state, value);
+            }
+        }
+        matrix
+    }
+
+    /// Population after `days` days, from the transition matrix raised to
+    /// the power `days` by repeated squaring, so it takes O(log days) matrix
+    /// products.
+    ///
+    /// The exact count for the puzzle's life cycle has about `days / 26`
+    /// digits, so past a few million days pass a `modulus`, which every
+    /// product is reduced by, to keep the numbers small.
+    pub fn population_after(&self, days: u64, modulus: Option<&BigUint>) -> BigUint {
+        let counts = self.big_counts();
+        let counts = self.transition().pow(days, modulus).apply(&counts, modulus);
+        let total = counts.iter().sum::<BigUint>();
+        match modulus {
+            Some(modulus) => total % modulus,
+            None => total,
+        }
+    }
+
+    /// First day the population is above `threshold`, looking no further than
+    /// `max_days`. The school itself isn't advanced.
+    pub fn first_day_above(&self, threshold: &BigUint, max_days: u64) -> Option<u64> {
+        let transition = self.transition();
+        let mut counts = self.big_counts();
+        for day in 0..=max_days {
+            if counts.iter().sum::<BigUint>() > *threshold {
+                return Some(day);
+            }
+            counts = transition.apply(&counts, None);
+        }
+        None
+    }
+
+    /// Exact population on each day from 0 to `days`. The school itself
+    /// isn't advanced.
+    pub fn totals(&self, days: u64) -> Vec<BigUint> {
+        let transition = self.transition();
+        let mut counts = self.big_counts();
+        let mut totals = vec![counts.iter().sum::<BigUint>()];
+        for _ in 0..days {
+            counts = transition.apply(&counts, None);
+            totals.push(counts.iter().sum());
+        }
+        totals
+    }
+}
@@ -0,0 +1,547 @@
+use crate::cost::FuelCost;
+use crate::crab::{Crab, Distance, Point};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Total fuel. Exact unless distances are Euclidean.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum Fuel {
+    Exact(u128),
+    Approximate(f64),
+    /// An exact total too large for a `u128`. It orders after every exact
+    /// total, so searches treat it as the most expensive.
+    Overflow,
+}
+impl Fuel {
+    /// Whether two totals are the same, allowing for rounding in
+    /// approximate ones.
+    pub fn matches(&self, other: &Fuel) -> bool {
+        match (self, other) {
+            (Fuel::Exact(a), Fuel::Exact(b)) => a == b,
+            (Fuel::Overflow, Fuel::Overflow) => true,
+            (Fuel::Approximate(a), Fuel::Approximate(b)) => {
+                (a - b).abs() <= 1e-9 * a.abs().max(b.abs()).max(1.0)
+            }
+            _ => false,
+        }
+    }
+}
+impl fmt::Display for Fuel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fuel::Exact(fuel) => write!(f, "{}", fuel),
+            Fuel::Approximate(fuel) => write!(f, "{:.3}", fuel),
+            Fuel::Overflow => write!(f, "overflow"),
+        }
+    }
+}
+
+/// Where the crabs line up and the fuel it takes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Alignment {
+    pub target: Point,
+    pub cost: Fuel,
+}
+
+/// How to look for the cheapest target.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Method {
+    /// The cost's closed form if it has one, else golden-section search if
+    /// it is convex, else brute force.
+    Auto,
+    ClosedForm,
+    Ternary,
+    Golden,
+    BruteForce,
+}
+impl FromStr for Method {
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Method, String> {
+        match str {
+            "auto" => Ok(Method::Auto),
+            "closed-form" => Ok(Method::ClosedForm),
+            "ternary" => Ok(Method::Ternary),
+            "golden" => Ok(Method::Golden),
+            "brute-force" => Ok(Method::BruteForce),
+            _ => Err(format!(
+                "Unknown method '{}', expected auto, closed-form, ternary, golden or brute-force",
+                str
+            )),
+        }
+    }
+}
+
+/// Fuel for every crab to move to `target`, `Fuel::Overflow` if an exact
+/// total doesn't fit. Fails if some crab's fuel is negative.
+pub fn total_cost(
+    crabs: &[Crab],
+    target: Point,
+    cost: &dyn FuelCost,
+    distance: Distance,
+) -> Result<Fuel, String> {
+    match distance {
+        Distance::Manhattan => {
+            let mut total = Some(0u128);
+            for crab in crabs {
+                let steps = crab
+                    .position
+                    .x
+                    .abs_diff(target.x)
+                    .checked_add(crab.position.y.abs_diff(target.y));
+                let fuel = match steps {
+                    Some(steps) => cost.cost(steps)?,
+                    None => None,
+                };
+                total = total.zip(fuel).and_then(|(total, fuel)| {
+                    total.checked_add(fuel.checked_mul(crab.weight as u128)?)
+                });
+            }
+            Ok(match total {
+                Some(total) => Fuel::Exact(total),
+                None => Fuel::Overflow,
+            })
+        }
+        Distance::Euclidean => {
+            let mut total = 0.0;
+            for crab in crabs {
+                let dx = crab.position.x as f64 - target.x as f64;
+                let dy = crab.position.y as f64 - target.y as f64;
+                let fuel = cost.cost_real(dx.hypot(dy));
+                if fuel < 0.0 {
+                    return Err(format!(
+                        "The {} cost is negative for a distance of {:.3}",
+                        cost.name(),
+                        dx.hypot(dy)
+                    ));
+                }
+                total += crab.weight as f64 * fuel;
+            }
+            Ok(Fuel::Approximate(total))
+        }
+    }
+}
+
+/// Cheapest of `targets`, the first one on ties.
+fn cheapest<I: Iterator<Item = Point>>(
+    crabs: &[Crab],
+    targets: I,
+    cost: &dyn FuelCost,
+    distance: Distance,
+) -> Result<Alignment, String> {
+    let mut best: Option<Alignment> = None;
+    for target in targets {
+        let alignment = Alignment {
+            target,
+            cost: total_cost(crabs, target, cost, distance)?,
+        };
+        if best.is_none_or(|best| alignment.cost < best.cost) {
+            best = Some(alignment);
+        }
+    }
+    Ok(best.expect("There are no targets"))
+}
+
+/// Whether `a` is cheaper than `b`, or as cheap and first in x then y
+/// order.
+fn before(a: &Alignment, b: &Alignment) -> bool {
+    a.cost < b.cost || (a.cost == b.cost && (a.target.x, a.target.y) < (b.target.x, b.target.y))
+}
+
+/// Smallest and largest corner of the crabs' bounding box.
+pub(crate) fn bounds(crabs: &[Crab]) -> (Point, Point) {
+    let x = crabs.iter().map(|crab| crab.position.x);
+    let y = crabs.iter().map(|crab| crab.position.y);
+    (
+        Point {
+            x: x.clone().min().expect("There are no crabs"),
+            y: y.clone().min().expect("There are no crabs"),
+        },
+        Point {
+            x: x.max().expect("There are no crabs"),
+            y: y.max().expect("There are no crabs"),
+        },
+    )
+}
+
+/// How distances are measured for `crabs`: on a line both kinds agree, and
+/// Manhattan keeps the totals exact.
+pub(crate) fn measure(crabs: &[Crab], distance: Distance) -> Distance {
+    let (low, high) = bounds(crabs);
+    if low.y == high.y {
+        Distance::Manhattan
+    } else {
+        distance
+    }
+}
+
+/// Finds the cheapest target for `crabs`, the first in x then y order on
+/// ties. Fails when every exact total overflows or some fuel is negative.
+///
+/// Crabs that all share a y coordinate are the puzzle's one-dimensional
+/// case, where Euclidean and Manhattan distances agree, so their totals are
+/// exact either way. Among the crabs on a plane, linear costs under
+/// Manhattan distance split into one problem per axis, which have closed
+/// forms. Other convex costs are searched along each row or column of the
+/// crabs' bounding box, which finds the cheapest target as long as there
+/// are at most `MAX_SCANNED_LINES` of one or the other. Beyond that the
+/// search is very likely but not guaranteed to find it, which
+/// `--method brute-force` is for.
+pub fn align(
+    crabs: &[Crab],
+    cost: &dyn FuelCost,
+    distance: Distance,
+    method: Method,
+) -> Result<Alignment, String> {
+    let distance = measure(crabs, distance);
+    if !cost.is_convex() && (method == Method::Ternary || method == Method::Golden) {
+        return Err(format!(
+            "The {} cost isn't known to be convex, so it can't be searched",
+            cost.name()
+        ));
+    }
+
+    let alignment = match method {
+        Method::Auto => match closed_form(crabs, cost, distance)? {
+            Some(alignment) => alignment,
+            None if cost.is_convex() => search(crabs, cost, distance, true)?,
+            None => brute_force(crabs, cost, distance)?,
+        },
+        Method::ClosedForm => closed_form(crabs, cost, distance)?
+            .ok_or(format!("The {} cost has no closed form here", cost.name()))?,
+        Method::Ternary => search(crabs, cost, distance, false)?,
+        Method::Golden => search(crabs, cost, distance, true)?,
+        Method::BruteForce => brute_force(crabs, cost, distance)?,
+    };
+    if alignment.cost == Fuel::Overflow {
+        return Err(format!(
+            "The total {} fuel overflows a u128 at every target",
+            cost.name()
+        ));
+    }
+    Ok(alignment)
+}
+
+/// Tries every target in the crabs' bounding box, O(n · area). This is the
+/// fallback for costs that aren't convex; as long as the cost never
+/// decreases with distance, no target outside the box is cheaper.
+pub fn brute_force(
+    crabs: &[Crab],
+    cost: &dyn FuelCost,
+    distance: Distance,
+) -> Result<Alignment, String> {
+    let (low, high) = bounds(crabs);
+    let targets = (low.x..=high.x).flat_map(|x| (low.y..=high.y).map(move |y| Point { x, y }));
+    cheapest(crabs, targets, cost, distance)
+}
+
+/// Each crab's coordinate along one axis, with its weight.
+fn axis(crabs: &[Crab], x: bool) -> Vec<(i64, u64)> {
+    crabs
+        .iter()
+        .map(|crab| {
+            let coordinate = if x { crab.position.x } else { crab.position.y };
+            (coordinate, crab.weight)
+        })
+        .collect()
+}
+
+fn closed_form(
+    crabs: &[Crab],
+    cost: &dyn FuelCost,
+    distance: Distance,
+) -> Result<Option<Alignment>, String> {
+    let (low, high) = bounds(crabs);
+    let Some((x_low, x_high)) = cost.closed_form(&axis(crabs, true)) else {
+        return Ok(None);
+    };
+    let y_range = if low.y == high.y {
+        Some((low.y, high.y))
+    } else if distance == Distance::Manhattan && cost.is_linear() {
+        cost.closed_form(&axis(crabs, false))
+    } else {
+        None
+    };
+    let Some((y_low, y_high)) = y_range else {
+        return Ok(None);
+    };
+    let targets = (x_low..=x_high).flat_map(|x| (y_low..=y_high).map(move |y| Point { x, y }));
+    cheapest(crabs, targets, cost, distance).map(Some)
+}
+
+/// Most rows or columns `search` goes through one by one.
+pub const MAX_SCANNED_LINES: u64 = 100_000;
+
+/// Searches every column of the crabs' bounding box, or every row if there
+/// are fewer, with `convex_minimum`. The total is convex along each of them,
+/// so this finds the cheapest target.
+///
+/// Boxes with too many rows and columns to go through are searched with
+/// `convex_minimum` across them too, which can miss the cheapest target:
+/// the cheapest total of each column isn't convex in the column over whole
+/// numbers. The result is then polished by stepping to cheaper neighbours
+/// for as long as there are any.
+fn search(
+    crabs: &[Crab],
+    cost: &dyn FuelCost,
+    distance: Distance,
+    golden: bool,
+) -> Result<Alignment, String> {
+    let (low, high) = bounds(crabs);
+    let by_column = low.x.abs_diff(high.x) <= low.y.abs_diff(high.y);
+    let (outer_low, outer_high, inner_low, inner_high) = if by_column {
+        (low.x, high.x, low.y, high.y)
+    } else {
+        (low.y, high.y, low.x, high.x)
+    };
+    let point = |outer: i64, inner: i64| {
+        if by_column {
+            Point { x: outer, y: inner }
+        } else {
+            Point { x: inner, y: outer }
+        }
+    };
+    let best_inner = |outer: i64| {
+        convex_minimum(inner_low, inner_high, golden, |inner| {
+            total_cost(crabs, point(outer, inner), cost, distance)
+        })
+    };
+
+    if outer_low.abs_diff(outer_high) < MAX_SCANNED_LINES {
+        let mut best: Option<Alignment> = None;
+        for outer in outer_low..=outer_high {
+            let (inner, fuel) = best_inner(outer)?;
+            let alignment = Alignment {
+                target: point(outer, inner),
+                cost: fuel,
+            };
+            if best.is_none_or(|best| before(&alignment, &best)) {
+                best = Some(alignment);
+            }
+        }
+        return Ok(best.unwrap());
+    }
+
+    let (outer, _) = convex_minimum(outer_low, outer_high, golden, |outer| {
+        Ok(best_inner(outer)?.1)
+    })?;
+    let (inner, fuel) = best_inner(outer)?;
+    let mut best = Alignment {
+        target: point(outer, inner),
+        cost: fuel,
+    };
+    // A linear total under Euclidean distance has a kink at every crab, and
+    // its minimum is often right on one.
+    if distance == Distance::Euclidean && cost.is_linear() {
+        let at_crabs = cheapest(
+            crabs,
+            crabs.iter().map(|crab| crab.position),
+            cost,
+            distance,
+        )?;
+        if at_crabs.cost < best.cost {
+            best = at_crabs;
+        }
+    }
+    loop {
+        let neighbours = (-1..=1)
+            .flat_map(|dx| (-1..=1).map(move |dy| (dx, dy)))
+            .filter_map(|(dx, dy)| {
+                Some(Point {
+                    x: best.target.x.checked_add(dx)?,
+                    y: best.target.y.checked_add(dy)?,
+                })
+            })
+            .filter(|target| {
+                target.x >= low.x && target.x <= high.x && target.y >= low.y && target.y <= high.y
+            });
+        let next = cheapest(crabs, neighbours, cost, distance)?;
+        if next.cost < best.cost {
+            best = next;
+        } else {
+            return Ok(best);
+        }
+    }
+}
+
+/// Lowest cheapest `t` in `low..=high` for a convex `f`, by ternary or
+/// golden-section search. Fails as soon as `f` does.
+///
+/// When both probes cost the same, a convex `f` has its minimum between
+/// them, but it may also continue flat to the left, so only the right end
+/// moves in to keep the lowest cheapest `t`. Golden-section search keeps
+/// the worse probe in the range, which puts the better one where the next
+/// step wants a probe, and reuses it.
+pub fn convex_minimum<F: FnMut(i64) -> Result<Fuel, String>>(
+    low: i64,
+    high: i64,
+    golden: bool,
+    mut f: F,
+) -> Result<(i64, Fuel), String> {
+    const SHORTER: f64 = 0.381_966_011_250_105_1;
+
+    let mut evaluated = HashMap::new();
+    let mut evaluate = |t: i64| -> Result<Fuel, String> {
+        if let Some(fuel) = evaluated.get(&t) {
+            return Ok(*fuel);
+        }
+        let fuel = f(t)?;
+        evaluated.insert(t, fuel);
+        Ok(fuel)
+    };
+
+    let (mut low, mut high) = (low, high);
+    // The span can be more than an i64 holds. Offsets from it are below the
+    // span, so moving `low` up or `high` down by one stays in range.
+    while high.abs_diff(low) > 2 {
+        let span = high.abs_diff(low);
+        let (left, right) = if golden {
+            let step = ((span as f64 * SHORTER).round() as u64).max(1);
+            if step < span - step {
+                (
+                    low.wrapping_add_unsigned(step),
+                    high.wrapping_sub_unsigned(step),
+                )
+            } else {
+                let middle = low.wrapping_add_unsigned(span / 2);
+                (middle, middle + 1)
+            }
+        } else {
+            let third = span / 3;
+            (
+                low.wrapping_add_unsigned(third),
+                high.wrapping_sub_unsigned(third),
+            )
+        };
+        let left_cost = evaluate(left)?;
+        let right_cost = evaluate(right)?;
+        if left_cost > right_cost {
+            low = if golden { left } else { left + 1 };
+        } else if left_cost < right_cost {
+            high = if golden { right } else { right - 1 };
+        } else {
+            high = right;
+        }
+    }
+
+    let mut best: Option<(i64, Fuel)> = None;
+    for t in low..=high {
+        let fuel = evaluate(t)?;
+        if best.is_none_or(|best| fuel < best.1) {
+            best = Some((t, fuel));
+        }
+    }
+    Ok(best.unwrap())
+}
+
+/// Lower weighted median of `(position, weight)` pairs, where a linear cost
+/// is cheapest.
+///
+/// Moving the target one step towards more weight than it moves away from
+/// saves fuel, so every target between the two middle crabs by weight is
+/// optimal, and the lower weighted median is the lowest of them.
+pub fn weighted_median(axis: &[(i64, u64)]) -> i64 {
+    let mut sorted = axis.to_vec();
+    sorted.sort_unstable();
+    // Weights are below 2⁶⁴ and there are fewer than 2⁶⁴ crabs, so neither
+    // sum overflows.
+    let total = sorted
+        .iter()
+        .map(|(_, weight)| *weight as u128)
+        .sum::<u128>();
+    let mut below = 0;
+    for (position, weight) in sorted {
+        below += weight as u128;
+        if 2 * below >= total {
+            return position;
+        }
+    }
+    unreachable!("There are no crabs")
+}
+
+/// Targets around the weighted mean of `(position, weight)` pairs, one of
+/// which is cheapest for a triangular cost.
+///
+/// Over real targets the total is convex with slope
+/// `Σ wᵢ(t - xᵢ) + ½ Σ wᵢ sign(t - xᵢ)`, and the second sum is at most half
+/// the total weight either way, so the real optimum lies within half a step
+/// of the weighted mean. The integer optimum is next to the real one, so
+/// only the integers from `floor(mean - ½)` to `ceil(mean + ½)` need
+/// checking. `None` if the weighted sum overflows.
+pub fn weighted_mean_range(axis: &[(i64, u64)]) -> Option<(i64, i64)> {
+    let mut total = 0i128;
+    let mut sum = 0i128;
+    for &(position, weight) in axis {
+        total = total.checked_add(weight as i128)?;
+        sum = sum.checked_add((position as i128).checked_mul(weight as i128)?)?;
+    }
+    let twice_sum = sum.checked_mul(2)?;
+    let twice_total = total.checked_mul(2)?;
+    let lowest = twice_sum.checked_sub(total)?.div_euclid(twice_total);
+    let highest = -(twice_sum.checked_add(total)?.checked_neg()?).div_euclid(twice_total);
+    Some((i64::try_from(lowest).ok()?, i64::try_from(highest).ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cost::{Linear, Quadratic, Triangular};
+    use crate::crab::{parse_line_crabs, parse_plane_crabs};
+
+    /// Crabs whose span is more than an i64 holds.
+    const FAR_APART: &str = "-9000000000000000000,9000000000000000000";
+
+    #[test]
+    fn searches_spans_wider_than_an_i64() {
+        let crabs = parse_line_crabs(FAR_APART).unwrap();
+        for method in [Method::Auto, Method::Ternary, Method::Golden] {
+            let alignment = align(&crabs, &Quadratic, Distance::Manhattan, method).unwrap();
+            assert_eq!(alignment.target, Point { x: 0, y: 0 });
+            assert_eq!(
+                alignment.cost,
+                Fuel::Exact(2 * 9_000_000_000_000_000_000u128.pow(2))
+            );
+        }
+    }
+
+    #[test]
+    fn closed_forms_cover_spans_wider_than_an_i64() {
+        let crabs = parse_line_crabs(FAR_APART).unwrap();
+        let triangular = align(&crabs, &Triangular, Distance::Manhattan, Method::ClosedForm);
+        assert_eq!(triangular.unwrap().target, Point { x: 0, y: 0 });
+        let linear = align(&crabs, &Linear, Distance::Manhattan, Method::ClosedForm);
+        assert_eq!(
+            linear.unwrap().cost,
+            Fuel::Exact(18_000_000_000_000_000_000)
+        );
+    }
+
+    #[test]
+    fn measures_euclidean_distances_wider_than_an_i64() {
+        let crabs = parse_plane_crabs(
+            "-9000000000000000000,-9000000000000000000\n9000000000000000000,9000000000000000000",
+        )
+        .unwrap();
+        let target = Point {
+            x: 9_000_000_000_000_000_000,
+            y: 9_000_000_000_000_000_000,
+        };
+        let fuel = total_cost(&crabs, target, &Linear, Distance::Euclidean).unwrap();
+        assert!(fuel.matches(&Fuel::Approximate(18e18 * 2f64.sqrt())));
+    }
+
+    #[test]
+    fn finds_the_cheapest_target_in_a_lattice_corner() {
+        // The cheapest target is on the heaviest crab, which single steps
+        // from the nested search's result at 12,-6 don't reach.
+        let crabs = parse_plane_crabs("-20,15*2\n-16,13\n14,-7*8\n-2,4\n-2,-3*4").unwrap();
+        let brute = align(&crabs, &Linear, Distance::Euclidean, Method::BruteForce).unwrap();
+        assert_eq!(brute.target, Point { x: 14, y: -7 });
+        for method in [Method::Auto, Method::Ternary, Method::Golden] {
+            let alignment = align(&crabs, &Linear, Distance::Euclidean, method).unwrap();
+            assert_eq!(alignment.target, brute.target);
+            assert!(alignment.cost.matches(&brute.cost));
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use crate::alignment::{align, Alignment, Method};
+use crate::cost::{parse_cost, FuelCost};
+use crate::crab::{parse_line_crabs, parse_plane_crabs, Distance};
+use crate::curve::Curve;
+use std::env;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::process;
+
+/// The solver behind part 1 and part 2, which only differ in the default
+/// cost.
+///
+/// Flags: `--cost` picks another cost, `--method` how to search for the
+/// cheapest target, `--brute-force` is short for `--method brute-force`,
+/// and `--check` also runs the brute force and compares them. `--plane`
+/// reads one `x,y` crab per line instead of the puzzle's single line, and
+/// `--distance` says how distances on the plane are measured.
+///
+/// `--curve FILE` writes the total fuel for every target to a CSV file and
+/// `--plot` draws it, for crabs on a line, to show how flat the minimum is.
+pub fn solve(default_cost: Box<dyn FuelCost>) -> io::Result<()> {
+    let mut cost = default_cost;
+    let mut method = Method::Auto;
+    let mut check = false;
+    let mut plane = false;
+    let mut distance = Distance::Manhattan;
+    let mut curve = None;
+    let mut plot = false;
+    let mut path = "./input.txt".to_string();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--cost" => cost = parse_cost(&args.next().unwrap()).unwrap(),
+            "--method" => method = args.next().unwrap().parse::<Method>().unwrap(),
+            "--brute-force" => method = Method::BruteForce,
+            "--check" => check = true,
+            "--plane" => plane = true,
+            "--distance" => distance = args.next().unwrap().parse::<Distance>().unwrap(),
+            "--curve" => curve = Some(args.next().unwrap()),
+            "--plot" => plot = true,
+            _ if arg.starts_with("--") => {
+                return Err(io::Error::other(format!("Unknown flag '{}'", arg)))
+            }
+            _ => path = arg,
+        }
+    }
+
+    let file = fs::File::open(path)?;
+    let mut reader = io::BufReader::new(file);
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+
+    let crabs = if plane {
+        parse_plane_crabs(&input)
+    } else {
+        parse_line_crabs(&input)
+    };
+    let crabs = match crabs {
+        Ok(crabs) => crabs,
+        Err(e) => panic!("{}", e),
+    };
+
+    let smallest = match align(&crabs, cost.as_ref(), distance, method) {
+        Ok(smallest) => smallest,
+        Err(e) => panic!("{}", e),
+    };
+    let target = |alignment: &Alignment| {
+        if plane {
+            alignment.target.to_string()
+        } else {
+            alignment.target.x.to_string()
+        }
+    };
+    println!(
+        "Smallest cost at {} with cost {}",
+        target(&smallest),
+        smallest.cost
+    );
+
+    if check {
+        let checked = align(&crabs, cost.as_ref(), distance, Method::BruteForce).unwrap();
+        if !checked.cost.matches(&smallest.cost) {
+            println!(
+                "Mismatch! Brute force found cost {} at {}",
+                checked.cost,
+                target(&checked)
+            );
+            process::exit(1);
+        }
+        println!("Brute force agrees");
+    }
+
+    if curve.is_some() || plot {
+        let costs = match Curve::new(&crabs, cost.as_ref(), distance) {
+            Ok(costs) => costs,
+            Err(e) => panic!("{}", e),
+        };
+        if plot {
+            costs.write_plot(&mut io::stdout(), &smallest, 72, 20)?;
+        }
+        println!(
+            "{} of {} targets cost within 1% of the smallest",
+            costs.count_within(&smallest, 0.01),
+            costs.points().len()
+        );
+        if let Some(curve) = curve {
+            let file = fs::File::create(&curve)?;
+            let mut writer = io::BufWriter::new(file);
+            costs.write_csv(&mut writer)?;
+            writer.flush()?;
+            println!("Wrote {} targets to {}", costs.points().len(), curve);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,208 @@
+use crate::alignment::{weighted_mean_range, weighted_median};
+
+/// Fuel a crab burns to move a number of steps.
+pub trait FuelCost {
+    /// Fuel to move `distance` steps, `None` if it doesn't fit in a `u128`.
+    /// Fails if the fuel is negative.
+    fn cost(&self, distance: u64) -> Result<Option<u128>, String>;
+    /// The same cost for a distance that isn't a whole number of steps.
+    fn cost_real(&self, distance: f64) -> f64;
+    /// Whether the cost is convex and never decreases with distance. The
+    /// total over all crabs is then convex in the target, so the cheapest
+    /// target can be searched for rather than tried one by one.
+    fn is_convex(&self) -> bool;
+    /// Whether the cost is `a·d + b` with `a ≥ 0`. Under Manhattan distance
+    /// the cheapest target is then found along each axis separately.
+    fn is_linear(&self) -> bool {
+        false
+    }
+    /// A range of targets holding the cheapest one for crabs on a line,
+    /// given as `(position, weight)`, when the cost has a shortcut for it.
+    fn closed_form(&self, axis: &[(i64, u64)]) -> Option<(i64, i64)> {
+        if self.is_linear() {
+            let median = weighted_median(axis);
+            Some((median, median))
+        } else {
+            None
+        }
+    }
+    fn name(&self) -> String;
+}
+
+/// One fuel per step.
+pub struct Linear;
+impl FuelCost for Linear {
+    fn cost(&self, distance: u64) -> Result<Option<u128>, String> {
+        Ok(Some(distance as u128))
+    }
+
+    fn cost_real(&self, distance: f64) -> f64 {
+        distance
+    }
+
+    fn is_convex(&self) -> bool {
+        true
+    }
+
+    fn is_linear(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> String {
+        "linear".to_string()
+    }
+}
+
+/// Each step costs one more than the previous one.
+pub struct Triangular;
+impl FuelCost for Triangular {
+    fn cost(&self, distance: u64) -> Result<Option<u128>, String> {
+        let distance = distance as u128;
+        Ok(distance
+            .checked_mul(distance + 1)
+            .map(|doubled| doubled / 2))
+    }
+
+    fn cost_real(&self, distance: f64) -> f64 {
+        distance * (distance + 1.0) / 2.0
+    }
+
+    fn is_convex(&self) -> bool {
+        true
+    }
+
+    fn closed_form(&self, axis: &[(i64, u64)]) -> Option<(i64, i64)> {
+        weighted_mean_range(axis)
+    }
+
+    fn name(&self) -> String {
+        "triangular".to_string()
+    }
+}
+
+/// The square of the distance.
+pub struct Quadratic;
+impl FuelCost for Quadratic {
+    fn cost(&self, distance: u64) -> Result<Option<u128>, String> {
+        let distance = distance as u128;
+        Ok(distance.checked_mul(distance))
+    }
+
+    fn cost_real(&self, distance: f64) -> f64 {
+        distance * distance
+    }
+
+    fn is_convex(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> String {
+        "quadratic".to_string()
+    }
+}
+
+/// `c₀ + c₁·d + c₂·d² + …` for a distance `d`.
+pub struct Polynomial {
+    /// From the constant term up. They may be negative, as long as the fuel
+    /// for the distances that come up isn't.
+    coefficients: Vec<i64>,
+}
+impl Polynomial {
+    pub fn new(coefficients: Vec<i64>) -> Result<Polynomial, String> {
+        if coefficients.is_empty() {
+            return Err("A polynomial needs at least one coefficient".to_string());
+        }
+        Ok(Polynomial { coefficients })
+    }
+}
+impl FuelCost for Polynomial {
+    /// Sums the positive and the negative terms apart, so the fuel is exact
+    /// whenever both sums fit in a `u128`.
+    fn cost(&self, distance: u64) -> Result<Option<u128>, String> {
+        let mut positive = Some(0u128);
+        let mut negative = Some(0u128);
+        let mut power = Some(1u128);
+        for coefficient in &self.coefficients {
+            if *coefficient != 0 {
+                let term =
+                    power.and_then(|power| power.checked_mul(coefficient.unsigned_abs() as u128));
+                let sum = if *coefficient > 0 {
+                    &mut positive
+                } else {
+                    &mut negative
+                };
+                *sum = sum.zip(term).and_then(|(sum, term)| sum.checked_add(term));
+            }
+            power = power.and_then(|power| power.checked_mul(distance as u128));
+        }
+
+        match (positive, negative) {
+            (Some(positive), Some(negative)) if positive >= negative => {
+                Ok(Some(positive - negative))
+            }
+            (Some(_), _) => Err(format!(
+                "The {} cost is negative for {} steps",
+                self.name(),
+                distance
+            )),
+            (None, _) => Ok(None),
+        }
+    }
+
+    fn cost_real(&self, distance: f64) -> f64 {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(0.0, |cost, coefficient| {
+                cost * distance + *coefficient as f64
+            })
+    }
+
+    /// Known to be when every coefficient but the constant one is
+    /// non-negative, which makes every term convex and non-decreasing for
+    /// non-negative distances. Polynomials that only get there with help
+    /// from a higher term, like `d² - d`, are treated as not convex.
+    fn is_convex(&self) -> bool {
+        self.coefficients[1..]
+            .iter()
+            .all(|coefficient| *coefficient >= 0)
+    }
+
+    fn is_linear(&self) -> bool {
+        self.coefficients.len() <= 2 && self.is_convex()
+    }
+
+    fn name(&self) -> String {
+        let coefficients = self
+            .coefficients
+            .iter()
+            .map(|coefficient| coefficient.to_string())
+            .collect::<Vec<String>>();
+        format!("poly:{}", coefficients.join(","))
+    }
+}
+
+/// A built-in cost by name, or `poly:c₀,c₁,…` for a polynomial.
+pub fn parse_cost(str: &str) -> Result<Box<dyn FuelCost>, String> {
+    match str {
+        "linear" => Ok(Box::new(Linear)),
+        "triangular" => Ok(Box::new(Triangular)),
+        "quadratic" => Ok(Box::new(Quadratic)),
+        _ => match str.strip_prefix("poly:") {
+            Some(coefficients) => Ok(Box::new(Polynomial::new(
+                coefficients
+                    .split(',')
+                    .map(|c| {
+                        c.trim()
+                            .parse::<i64>()
+                            .map_err(|e| format!("Couldn't parse coefficient '{}': {}", c, e))
+                    })
+                    .collect::<Result<Vec<i64>, String>>()?,
+            )?)),
+            None => Err(format!(
+                "Unknown cost '{}', expected linear, triangular, quadratic or poly:c0,c1,...",
+                str
+            )),
+        },
+    }
+}
@@ -0,0 +1,114 @@
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Crab {
+    pub position: Point,
+    /// Multiplies the fuel the crab burns, for subs that are less
+    /// efficient than others.
+    pub weight: u64,
+}
+
+/// How far a crab is from the target.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Distance {
+    /// Steps along each axis, added up.
+    Manhattan,
+    /// Straight line distance, which isn't a whole number of steps in
+    /// general, so totals under it are approximate.
+    Euclidean,
+}
+impl FromStr for Distance {
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Distance, String> {
+        match str {
+            "manhattan" => Ok(Distance::Manhattan),
+            "euclidean" => Ok(Distance::Euclidean),
+            _ => Err(format!(
+                "Unknown distance '{}', expected manhattan or euclidean",
+                str
+            )),
+        }
+    }
+}
+
+/// Parses a weight suffix, `*w`, off a crab. Crabs without one weigh 1.
+fn split_weight(str: &str) -> Result<(&str, u64), String> {
+    match str.split_once('*') {
+        Some((position, weight)) => {
+            let weight = weight
+                .trim()
+                .parse::<u64>()
+                .map_err(|e| format!("Couldn't parse weight of '{}': {}", str, e))?;
+            if weight == 0 {
+                return Err(format!("Weight of '{}' must be at least 1", str));
+            }
+            Ok((position, weight))
+        }
+        None => Ok((str, 1)),
+    }
+}
+
+/// Parses crabs on a line, as in the puzzle: comma separated positions, each
+/// optionally followed by `*weight`. They all get y = 0.
+pub fn parse_line_crabs(input: &str) -> Result<Vec<Crab>, String> {
+    let crabs = input
+        .trim()
+        .split(',')
+        .map(|str| {
+            let (x, weight) = split_weight(str.trim())?;
+            let x = x
+                .trim()
+                .parse::<i64>()
+                .map_err(|e| format!("Couldn't parse numbers: {}", e))?;
+            Ok(Crab {
+                position: Point { x, y: 0 },
+                weight,
+            })
+        })
+        .collect::<Result<Vec<Crab>, String>>()?;
+    Ok(crabs)
+}
+
+/// Parses crabs on a plane, one `x,y` per line, each optionally followed by
+/// `*weight`.
+pub fn parse_plane_crabs(input: &str) -> Result<Vec<Crab>, String> {
+    let crabs = input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (position, weight) = split_weight(line.trim())?;
+            let (x, y) = position
+                .split_once(',')
+                .ok_or(format!("Couldn't parse crab '{}', expected x,y", line))?;
+            let parse = |n: &str| {
+                n.trim()
+                    .parse::<i64>()
+                    .map_err(|e| format!("Couldn't parse crab '{}': {}", line, e))
+            };
+            Ok(Crab {
+                position: Point {
+                    x: parse(x)?,
+                    y: parse(y)?,
+                },
+                weight,
+            })
+        })
+        .collect::<Result<Vec<Crab>, String>>()?;
+    if crabs.is_empty() {
+        return Err("There are no crabs".to_string());
+    }
+    Ok(crabs)
+}
@@ -0,0 +1,180 @@
+use crate::alignment::{bounds, measure, total_cost, Alignment, Fuel};
+use crate::cost::FuelCost;
+use crate::crab::{Crab, Distance, Point};
+use std::io;
+use std::io::prelude::*;
+
+/// Most targets `Curve::new` agrees to cost.
+pub const MAX_CURVE_TARGETS: u128 = 1_000_000;
+
+/// Total fuel for every target in the crabs' bounding box.
+pub struct Curve {
+    /// In x then y order.
+    points: Vec<Alignment>,
+    on_a_line: bool,
+}
+impl Curve {
+    pub fn new(crabs: &[Crab], cost: &dyn FuelCost, distance: Distance) -> Result<Curve, String> {
+        let (low, high) = bounds(crabs);
+        let targets = (low.x.abs_diff(high.x) as u128 + 1) * (low.y.abs_diff(high.y) as u128 + 1);
+        if targets > MAX_CURVE_TARGETS {
+            return Err(format!(
+                "{} targets are too many to cost, the limit is {}",
+                targets, MAX_CURVE_TARGETS
+            ));
+        }
+
+        let distance = measure(crabs, distance);
+        let points = (low.x..=high.x)
+            .flat_map(|x| (low.y..=high.y).map(move |y| Point { x, y }))
+            .map(|target| {
+                Ok(Alignment {
+                    target,
+                    cost: total_cost(crabs, target, cost, distance)?,
+                })
+            })
+            .collect::<Result<Vec<Alignment>, String>>()?;
+        Ok(Curve {
+            points,
+            on_a_line: low.y == high.y,
+        })
+    }
+
+    pub fn points(&self) -> &[Alignment] {
+        &self.points
+    }
+
+    /// Number of targets whose total is at most `fraction` above
+    /// `optimum`'s.
+    pub fn count_within(&self, optimum: &Alignment, fraction: f64) -> usize {
+        let limit = value(&optimum.cost) * (1.0 + fraction);
+        self.points
+            .iter()
+            .filter(|point| value(&point.cost) <= limit)
+            .count()
+    }
+
+    /// One line per target, `target,cost` for crabs on a line and
+    /// `x,y,cost` for crabs on a plane.
+    pub fn write_csv<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        if self.on_a_line {
+            writeln!(writer, "target,cost")?;
+        } else {
+            writeln!(writer, "x,y,cost")?;
+        }
+        for point in &self.points {
+            if self.on_a_line {
+                writeln!(writer, "{},{}", point.target.x, point.cost)?;
+            } else {
+                writeln!(writer, "{},{}", point.target, point.cost)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Plots the curve for crabs on a line in `width` by `height`
+    /// characters, with the cheapest total of the targets in each column.
+    /// `optimum` is drawn as `O` and marked with `^` below the axis.
+    /// Overflowed totals are drawn at the top.
+    pub fn write_plot<W: Write>(
+        &self,
+        writer: &mut W,
+        optimum: &Alignment,
+        width: usize,
+        height: usize,
+    ) -> io::Result<()> {
+        if !self.on_a_line {
+            return Err(io::Error::other(
+                "Only crabs on a line can be plotted, export the curve as CSV instead",
+            ));
+        }
+
+        let columns = width.min(self.points.len()).max(1);
+        let height = height.max(2);
+        let column_of = |i: usize| i * columns / self.points.len();
+        let mut lowest = vec![f64::INFINITY; columns];
+        for (i, point) in self.points.iter().enumerate() {
+            let column = column_of(i);
+            lowest[column] = lowest[column].min(value(&point.cost));
+        }
+        let optimum_column = column_of((optimum.target.x - self.points[0].target.x) as usize);
+
+        let finite = lowest.iter().copied().filter(|cost| cost.is_finite());
+        let bottom = finite.clone().fold(f64::INFINITY, f64::min);
+        let top = finite.fold(f64::NEG_INFINITY, f64::max);
+        let row_of = |cost: f64| {
+            if !cost.is_finite() {
+                height - 1
+            } else if top > bottom {
+                ((cost - bottom) / (top - bottom) * (height - 1) as f64).round() as usize
+            } else {
+                0
+            }
+        };
+
+        let top_label = format!("{}", top);
+        let bottom_label = format!("{}", bottom);
+        let label_width = top_label.len().max(bottom_label.len());
+        for row in (0..height).rev() {
+            let label = if row == height - 1 {
+                &top_label
+            } else if row == 0 {
+                &bottom_label
+            } else {
+                ""
+            };
+            let line = lowest
+                .iter()
+                .enumerate()
+                .map(|(column, cost)| {
+                    if row_of(*cost) != row {
+                        ' '
+                    } else if column == optimum_column {
+                        'O'
+                    } else {
+                        '*'
+                    }
+                })
+                .collect::<String>();
+            writeln!(
+                writer,
+                "{:>w$} |{}",
+                label,
+                line.trim_end(),
+                w = label_width
+            )?;
+        }
+        writeln!(
+            writer,
+            "{:>w$} +{}",
+            "",
+            "-".repeat(columns),
+            w = label_width
+        )?;
+        writeln!(
+            writer,
+            "{:>w$}  {}^ {}",
+            "",
+            " ".repeat(optimum_column),
+            optimum.target.x,
+            w = label_width
+        )?;
+        writeln!(
+            writer,
+            "{:>w$}  targets {} to {}",
+            "",
+            self.points[0].target.x,
+            self.points[self.points.len() - 1].target.x,
+            w = label_width
+        )
+    }
+}
+
+/// A total as a float, infinite if it overflowed.
+fn value(fuel: &Fuel) -> f64 {
+    match fuel {
+        Fuel::Exact(fuel) => *fuel as f64,
+        Fuel::Approximate(fuel) => *fuel,
+        Fuel::Overflow => f64::INFINITY,
+    }
+}
@@ -0,0 +1,10 @@
+pub mod alignment;
+pub mod cli;
+pub mod cost;
+pub mod crab;
+pub mod curve;
+
+pub use alignment::{align, Alignment, Fuel, Method};
+pub use cost::{parse_cost, FuelCost, Linear, Polynomial, Quadratic, Triangular};
+pub use crab::{parse_line_crabs, parse_plane_crabs, Crab, Distance, Point};
+pub use curve::Curve;
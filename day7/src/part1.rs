@@ -1,42 +1,16 @@
-use std::fmt;
-use std::fs;
+use day7::cli::solve;
+use day7::Linear;
 use std::io;
-use std::io::prelude::*;
-use std::ops::{Add, Sub};
 
+/// Aligns the crabs where the total distance they move is smallest.
+///
+/// Usage: part1 [--cost linear|triangular|quadratic|poly:c0,c1,...]
+///              [--method auto|closed-form|ternary|golden|brute-force]
+///              [--brute-force] [--check] [--plane]
+///              [--distance manhattan|euclidean] [--curve FILE] [--plot]
+///              [input file]
+///
+/// The same solver as part 2, which only differs in the default cost.
 fn main() -> io::Result<()> {
-    let file = fs::File::open("./input.txt")?;
-    let mut reader = io::BufReader::new(file);
-    let mut input = String::new();
-    reader.read_to_string(&mut input)?;
-
-    let numbers: Vec<i32> = match input
-        .trim()
-        .split(",")
-        .map(|str| str.parse::<i32>())
-        .collect()
-    {
-        Ok(numbers) => numbers,
-        Err(e) => panic!("Couldn't parse numbers: {}", e),
-    };
-
-    let highest_number = *numbers.iter().reduce(|a, b| a.max(b)).unwrap();
-
-    let fuel_costs: Vec<i32> = (0..=highest_number)
-        .map(|target| {
-            numbers
-                .iter()
-                .fold(0, |fuel, number| fuel + (number - target).abs())
-        })
-        .collect();
-
-    let smallest = fuel_costs
-        .iter()
-        .enumerate()
-        .reduce(|a, b| if a.1 < b.1 { a } else { b })
-        .unwrap();
-
-    println!("Smallest cost at {} with cost {}", smallest.0, smallest.1);
-
-    Ok(())
+    solve(Box::new(Linear))
 }
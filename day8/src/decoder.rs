@@ -0,0 +1,208 @@
+use crate::glyphs::{parse_pattern, pattern_to_string, GlyphTable};
+use crate::note::Note;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Which segment each wire lights.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Wiring {
+    segment_of: Vec<usize>,
+}
+impl Wiring {
+    /// Parses the wire lighting each segment, from `a` on, like `deafgbc`
+    /// for the puzzle's example where wire `d` lights segment `a`.
+    pub fn parse(str: &str, segments: usize) -> Result<Wiring, String> {
+        let wires = str.trim();
+        parse_pattern(wires, segments).map_err(|e| format!("Wiring {}", e))?;
+        if wires.len() != segments {
+            return Err(format!(
+                "Wiring '{}' needs a wire for each of the {} segments",
+                wires, segments
+            ));
+        }
+        let mut segment_of = vec![0; segments];
+        for (segment, wire) in wires.bytes().enumerate() {
+            segment_of[(wire - b'a') as usize] = segment;
+        }
+        Ok(Wiring { segment_of })
+    }
+
+    /// A wiring picked uniformly at random.
+    pub fn random<R: Rng>(segments: usize, rng: &mut R) -> Wiring {
+        let mut segment_of = (0..segments).collect::<Vec<usize>>();
+        segment_of.shuffle(rng);
+        Wiring { segment_of }
+    }
+
+    pub fn segment_of(&self, wire: usize) -> usize {
+        self.segment_of[wire]
+    }
+
+    /// The segments lit by a pattern of wires.
+    pub fn apply(&self, pattern: u32) -> u32 {
+        self.segment_of
+            .iter()
+            .enumerate()
+            .filter(|(wire, _)| pattern & (1 << wire) != 0)
+            .fold(0, |segments, (_, segment)| segments | 1 << segment)
+    }
+
+    /// The wires that light `segments`, undoing `apply`.
+    pub fn wires_for(&self, segments: u32) -> u32 {
+        self.segment_of
+            .iter()
+            .enumerate()
+            .filter(|(_, segment)| segments & (1 << *segment) != 0)
+            .fold(0, |wires, (wire, _)| wires | 1 << wire)
+    }
+}
+
+/// Finds the only wiring that shows every signal and output pattern of
+/// `note` as a glyph of `table`, with different signals showing different
+/// glyphs.
+///
+/// Each wire starts out able to light any segment. A pattern can only show
+/// a glyph with as many segments, so its wires must light segments of one
+/// of those glyphs, and the other wires segments outside at least one of
+/// them. What is left is searched wire by wire, starting with the most
+/// constrained, and a branch is dropped as soon as some pattern can't
+/// become any glyph of its size.
+pub fn decode(table: &GlyphTable, note: &Note) -> Result<Wiring, String> {
+    let wires = table.segments();
+    let all = (1 << wires) - 1;
+
+    for (i, signal) in note.signals.iter().enumerate() {
+        if note.signals[..i].contains(signal) {
+            return Err(format!(
+                "Entry {}: signal '{}' is listed twice",
+                note.line,
+                pattern_to_string(*signal)
+            ));
+        }
+    }
+
+    let mut patterns = Vec::new();
+    let mut candidates = vec![all; wires];
+    for pattern in note.signals.iter().chain(&note.outputs) {
+        let glyphs = table.of_size(pattern.count_ones());
+        if glyphs.is_empty() {
+            return Err(format!(
+                "Entry {}: no glyph lights {} segments, as '{}' would need",
+                note.line,
+                pattern.count_ones(),
+                pattern_to_string(*pattern)
+            ));
+        }
+        let lit = glyphs.iter().fold(0, |lit, glyph| lit | glyph);
+        let always_lit = glyphs.iter().fold(all, |always, glyph| always & glyph);
+        for (wire, candidates) in candidates.iter_mut().enumerate() {
+            if pattern & (1 << wire) != 0 {
+                *candidates &= lit;
+            } else {
+                *candidates &= !always_lit;
+            }
+        }
+        patterns.push((*pattern, glyphs));
+    }
+
+    let mut order = (0..wires).collect::<Vec<usize>>();
+    order.sort_by_key(|wire| candidates[*wire].count_ones());
+    let mut search = Search {
+        patterns,
+        candidates,
+        order,
+        segment_of: vec![0; wires],
+        assigned: 0,
+        used: 0,
+        solutions: Vec::new(),
+    };
+    search.assign(0);
+
+    match search.solutions.len() {
+        0 => Err(format!(
+            "Entry {}: no wiring shows every pattern as a glyph",
+            note.line
+        )),
+        1 => Ok(search.solutions.pop().unwrap()),
+        _ => Err(format!(
+            "Entry {}: several wirings fit, the patterns don't tell them apart",
+            note.line
+        )),
+    }
+}
+
+/// Decodes the output value of `note` into the symbols it shows.
+pub fn read_output(table: &GlyphTable, note: &Note) -> Result<String, String> {
+    let wiring = decode(table, note)?;
+    note.outputs
+        .iter()
+        .map(|output| {
+            table.symbol(wiring.apply(*output)).ok_or(format!(
+                "Entry {}: output '{}' isn't a glyph",
+                note.line,
+                pattern_to_string(*output)
+            ))
+        })
+        .collect()
+}
+
+struct Search {
+    /// Each pattern with the glyphs it could show.
+    patterns: Vec<(u32, Vec<u32>)>,
+    /// Segments each wire may still light.
+    candidates: Vec<u32>,
+    /// Wires in the order they are assigned.
+    order: Vec<usize>,
+    segment_of: Vec<usize>,
+    /// Wires assigned so far.
+    assigned: u32,
+    /// Segments lit by them.
+    used: u32,
+    /// Stops at two, which is enough to know the wiring isn't unique.
+    solutions: Vec<Wiring>,
+}
+impl Search {
+    fn assign(&mut self, i: usize) {
+        if self.solutions.len() > 1 {
+            return;
+        }
+        if i == self.order.len() {
+            self.solutions.push(Wiring {
+                segment_of: self.segment_of.clone(),
+            });
+            return;
+        }
+
+        let wire = self.order[i];
+        let free = self.candidates[wire] & !self.used;
+        for segment in (0..self.segment_of.len()).filter(|segment| free & (1 << segment) != 0) {
+            self.segment_of[wire] = segment;
+            self.assigned |= 1 << wire;
+            self.used |= 1 << segment;
+            if self.consistent() {
+                self.assign(i + 1);
+            }
+            self.assigned &= !(1 << wire);
+            self.used &= !(1 << segment);
+        }
+    }
+
+    /// Whether every pattern can still show one of its glyphs: the
+    /// assigned wires in it light segments of the glyph, and the assigned
+    /// wires outside it don't.
+    fn consistent(&self) -> bool {
+        self.patterns.iter().all(|(pattern, glyphs)| {
+            let inside = self.image(pattern & self.assigned);
+            let outside = self.image(!pattern & self.assigned);
+            glyphs
+                .iter()
+                .any(|glyph| inside & !glyph == 0 && outside & glyph == 0)
+        })
+    }
+
+    fn image(&self, wires: u32) -> u32 {
+        (0..self.segment_of.len())
+            .filter(|wire| wires & (1 << wire) != 0)
+            .fold(0, |segments, wire| segments | 1 << self.segment_of[wire])
+    }
+}
@@ -0,0 +1,75 @@
+use day8::{encode, GlyphTable, Wiring};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::env;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+
+/// Writes notes that part1 and part2 can unscramble, one line per value to
+/// show.
+///
+/// Usage: encode [--glyphs 7|7-hex|14|16|FILE] [--wiring WIRES] [--seed S]
+///               [values file]
+///
+/// Reads the values, one per line like `5353`, from standard input unless a
+/// file is given. Each display gets its own random wiring unless
+/// `--wiring` gives the wire for each segment, like `deafgbc` for the
+/// puzzle's example. `--seed` makes the output repeatable.
+fn main() -> io::Result<()> {
+    let mut glyphs = "7".to_string();
+    let mut wiring = None;
+    let mut seed = None;
+    let mut path = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--glyphs" => glyphs = args.next().unwrap(),
+            "--wiring" => wiring = Some(args.next().unwrap()),
+            "--seed" => seed = Some(args.next().unwrap().parse::<u64>().unwrap()),
+            _ => path = Some(arg),
+        }
+    }
+
+    let table = match GlyphTable::load(&glyphs) {
+        Ok(table) => table,
+        Err(e) => panic!("{}", e),
+    };
+    let wiring = wiring.map(|wiring| match Wiring::parse(&wiring, table.segments()) {
+        Ok(wiring) => wiring,
+        Err(e) => panic!("{}", e),
+    });
+
+    let mut input = String::new();
+    match path {
+        Some(path) => {
+            let file = fs::File::open(path)?;
+            let mut reader = io::BufReader::new(file);
+            reader.read_to_string(&mut input)?;
+        }
+        None => {
+            io::stdin().read_to_string(&mut input)?;
+        }
+    }
+
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    for value in input
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+    {
+        let display_wiring = match &wiring {
+            Some(wiring) => wiring.clone(),
+            None => Wiring::random(table.segments(), &mut rng),
+        };
+        match encode(&table, &display_wiring, value, &mut rng) {
+            Ok(line) => println!("{}", line),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    Ok(())
+}
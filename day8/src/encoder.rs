@@ -0,0 +1,54 @@
+use crate::decoder::Wiring;
+use crate::glyphs::{pattern_to_string, GlyphTable};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Writes a line of notes for a display wired by `wiring` that shows
+/// `output`: every glyph of `table` as a signal pattern, then ` | ` and the
+/// pattern of each symbol of `output`. The signals are shuffled, as are the
+/// wires within each pattern, like in the puzzle's notes.
+pub fn encode<R: Rng>(
+    table: &GlyphTable,
+    wiring: &Wiring,
+    output: &str,
+    rng: &mut R,
+) -> Result<String, String> {
+    let outputs = output
+        .chars()
+        .map(|symbol| {
+            table
+                .glyphs()
+                .iter()
+                .find(|glyph| glyph.symbol == symbol)
+                .map(|glyph| glyph.segments)
+                .ok_or(format!("'{}' in '{}' isn't a glyph", symbol, output))
+        })
+        .collect::<Result<Vec<u32>, String>>()?;
+    if outputs.is_empty() {
+        return Err("There is no output to show".to_string());
+    }
+
+    let mut glyphs = table
+        .glyphs()
+        .iter()
+        .map(|glyph| glyph.segments)
+        .collect::<Vec<u32>>();
+    glyphs.shuffle(rng);
+
+    let mut scramble = |segments: u32| {
+        let mut wires = pattern_to_string(wiring.wires_for(segments))
+            .chars()
+            .collect::<Vec<char>>();
+        wires.shuffle(rng);
+        wires.into_iter().collect::<String>()
+    };
+    let signals = glyphs
+        .into_iter()
+        .map(&mut scramble)
+        .collect::<Vec<String>>();
+    let outputs = outputs
+        .into_iter()
+        .map(&mut scramble)
+        .collect::<Vec<String>>();
+    Ok(format!("{} | {}", signals.join(" "), outputs.join(" ")))
+}
@@ -0,0 +1,342 @@
+use std::fs;
+
+/// A symbol and the segments lit to show it, as a bit per segment with
+/// segment `a` the lowest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Glyph {
+    pub symbol: char,
+    pub segments: u32,
+}
+
+/// Most segments a display can have, one per letter.
+pub const MAX_SEGMENTS: usize = 26;
+
+/// Letters lit by the 0-9 and A-Z glyphs of a 14-segment display:
+///
+/// ```text
+///  aaaaaaa
+/// fi  j  kb
+/// f i j k b
+///  ggg hhh
+/// e n m l c
+/// en  m  lc
+///  ddddddd
+/// ```
+const FOURTEEN_SEGMENT: [(char, &str); 36] = [
+    ('0', "abcdefkn"),
+    ('1', "bc"),
+    ('2', "abdegh"),
+    ('3', "abcdh"),
+    ('4', "bcfgh"),
+    ('5', "adfgl"),
+    ('6', "acdefgh"),
+    ('7', "abc"),
+    ('8', "abcdefgh"),
+    ('9', "abcdfgh"),
+    ('A', "abcefgh"),
+    ('B', "abcdhjm"),
+    ('C', "adef"),
+    ('D', "abcdjm"),
+    ('E', "adefgh"),
+    ('F', "aefg"),
+    ('G', "acdefh"),
+    ('H', "bcefgh"),
+    ('I', "adjm"),
+    ('J', "bcde"),
+    ('K', "efgkl"),
+    ('L', "def"),
+    ('M', "bcefik"),
+    ('N', "bcefil"),
+    ('O', "abcdef"),
+    ('P', "abefgh"),
+    ('Q', "abcdefl"),
+    ('R', "abefghl"),
+    ('S', "acdfgh"),
+    ('T', "ajm"),
+    ('U', "bcdef"),
+    ('V', "efkn"),
+    ('W', "bcefln"),
+    ('X', "ikln"),
+    ('Y', "ikm"),
+    ('Z', "adkn"),
+];
+
+/// The symbols a display can show.
+pub struct GlyphTable {
+    segments: usize,
+    glyphs: Vec<Glyph>,
+}
+impl GlyphTable {
+    /// A table of `glyphs` for a display with `segments` segments. Every
+    /// glyph needs a symbol and a set of segments of its own, or notes
+    /// couldn't be read back.
+    pub fn new(segments: usize, glyphs: Vec<Glyph>) -> Result<GlyphTable, String> {
+        if segments == 0 || segments > MAX_SEGMENTS {
+            return Err(format!(
+                "A display has 1 to {} segments, not {}",
+                MAX_SEGMENTS, segments
+            ));
+        }
+        for (i, glyph) in glyphs.iter().enumerate() {
+            if glyph.segments == 0 || glyph.segments >> segments != 0 {
+                return Err(format!(
+                    "Glyph '{}' must light some of segments a to {}",
+                    glyph.symbol,
+                    wire_name(segments - 1)
+                ));
+            }
+            for other in &glyphs[..i] {
+                if other.symbol == glyph.symbol {
+                    return Err(format!("Glyph '{}' is defined twice", glyph.symbol));
+                }
+                if other.segments == glyph.segments {
+                    return Err(format!(
+                        "Glyphs '{}' and '{}' both light '{}'",
+                        other.symbol,
+                        glyph.symbol,
+                        pattern_to_string(glyph.segments)
+                    ));
+                }
+            }
+        }
+        Ok(GlyphTable { segments, glyphs })
+    }
+
+    /// A built-in table by name: `7` for the puzzle's digits, `7-hex` for
+    /// them and A to F, and `14` or `16` for 0-9 and A-Z.
+    pub fn builtin(name: &str) -> Option<GlyphTable> {
+        match name {
+            "7" => Some(GlyphTable::seven_segment()),
+            "7-hex" => Some(GlyphTable::seven_segment_hex()),
+            "14" => Some(GlyphTable::fourteen_segment()),
+            "16" => Some(GlyphTable::sixteen_segment()),
+            _ => None,
+        }
+    }
+
+    /// A built-in table, or else one read from the file `name`.
+    pub fn load(name: &str) -> Result<GlyphTable, String> {
+        match GlyphTable::builtin(name) {
+            Some(table) => Ok(table),
+            None => {
+                let definition = fs::read_to_string(name).map_err(|e| {
+                    format!(
+                        "'{}' is neither 7, 7-hex, 14 nor 16, and can't be read: {}",
+                        name, e
+                    )
+                })?;
+                parse_glyph_table(&definition)
+            }
+        }
+    }
+
+    /// The ten digits of a seven-segment display, as in the puzzle:
+    ///
+    /// ```text
+    ///  aaaa
+    /// b    c
+    /// b    c
+    ///  dddd
+    /// e    f
+    /// e    f
+    ///  gggg
+    /// ```
+    pub fn seven_segment() -> GlyphTable {
+        let glyphs = [
+            ('0', "abcefg"),
+            ('1', "cf"),
+            ('2', "acdeg"),
+            ('3', "acdfg"),
+            ('4', "bcdf"),
+            ('5', "abdfg"),
+            ('6', "abdefg"),
+            ('7', "acf"),
+            ('8', "abcdefg"),
+            ('9', "abcdfg"),
+        ]
+        .iter()
+        .map(|(symbol, segments)| Glyph {
+            symbol: *symbol,
+            segments: parse_pattern(segments, 7).unwrap(),
+        })
+        .collect();
+        GlyphTable {
+            segments: 7,
+            glyphs,
+        }
+    }
+
+    /// The seven-segment digits and the hexadecimal letters, with B and D
+    /// in lower case so they don't look like 8 and 0.
+    pub fn seven_segment_hex() -> GlyphTable {
+        let mut table = GlyphTable::seven_segment();
+        table.glyphs.extend(
+            [
+                ('A', "abcdef"),
+                ('b', "bdefg"),
+                ('C', "abeg"),
+                ('d', "cdefg"),
+                ('E', "abdeg"),
+                ('F', "abde"),
+            ]
+            .iter()
+            .map(|(symbol, segments)| Glyph {
+                symbol: *symbol,
+                segments: parse_pattern(segments, 7).unwrap(),
+            }),
+        );
+        table
+    }
+
+    pub fn fourteen_segment() -> GlyphTable {
+        let glyphs = FOURTEEN_SEGMENT
+            .iter()
+            .map(|(symbol, segments)| Glyph {
+                symbol: *symbol,
+                segments: parse_pattern(segments, 14).unwrap(),
+            })
+            .collect();
+        GlyphTable {
+            segments: 14,
+            glyphs,
+        }
+    }
+
+    /// The 14-segment glyphs on a display whose top and bottom segments are
+    /// split in two, lighting both halves. Only 1, with a flag, and J, with
+    /// a hook, light a single half; without them the halves couldn't be
+    /// told apart when unscrambling.
+    ///
+    /// ```text
+    ///  aaa bbb
+    /// hk  l  mc
+    /// h k l m c
+    ///  iii jjj
+    /// g p o n d
+    /// gp  o  nd
+    ///  fff eee
+    /// ```
+    pub fn sixteen_segment() -> GlyphTable {
+        // Segments of the 14-segment display, a to n, on this one.
+        const SPLIT: [&str; 14] = [
+            "ab", "c", "d", "ef", "g", "h", "i", "j", "k", "l", "m", "n", "o", "p",
+        ];
+        let glyphs = FOURTEEN_SEGMENT
+            .iter()
+            .map(|(symbol, segments)| {
+                let segments = parse_pattern(segments, 14).unwrap();
+                let split = match symbol {
+                    '1' => "alo".to_string(),
+                    'J' => "abfglo".to_string(),
+                    _ => SPLIT
+                        .iter()
+                        .enumerate()
+                        .filter(|(segment, _)| segments & (1 << segment) != 0)
+                        .map(|(_, halves)| *halves)
+                        .collect::<String>(),
+                };
+                Glyph {
+                    symbol: *symbol,
+                    segments: parse_pattern(&split, 16).unwrap(),
+                }
+            })
+            .collect();
+        GlyphTable {
+            segments: 16,
+            glyphs,
+        }
+    }
+
+    /// Number of segments, which are named from `a` on.
+    pub fn segments(&self) -> usize {
+        self.segments
+    }
+
+    pub fn glyphs(&self) -> &[Glyph] {
+        &self.glyphs
+    }
+
+    /// The symbol shown by exactly `segments`.
+    pub fn symbol(&self, segments: u32) -> Option<char> {
+        self.glyphs
+            .iter()
+            .find(|glyph| glyph.segments == segments)
+            .map(|glyph| glyph.symbol)
+    }
+
+    /// Segments of the glyphs that light `size` segments.
+    pub fn of_size(&self, size: u32) -> Vec<u32> {
+        self.glyphs
+            .iter()
+            .map(|glyph| glyph.segments)
+            .filter(|segments| segments.count_ones() == size)
+            .collect()
+    }
+}
+
+/// Parses a glyph table with one `symbol segments` glyph per line, like
+/// `7 acf`. Blank lines and lines starting with `#` are skipped. The display
+/// has as many segments as the last letter used says.
+pub fn parse_glyph_table(definition: &str) -> Result<GlyphTable, String> {
+    let mut glyphs = Vec::new();
+    for (i, line) in definition.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (symbol, segments) = line.split_once(char::is_whitespace).ok_or(format!(
+            "Line {}: expected a symbol and its segments",
+            i + 1
+        ))?;
+        let mut chars = symbol.chars();
+        let symbol = match (chars.next(), chars.next()) {
+            (Some(symbol), None) => symbol,
+            _ => return Err(format!("Line {}: '{}' isn't one symbol", i + 1, symbol)),
+        };
+        let segments = parse_pattern(segments.trim(), MAX_SEGMENTS)
+            .map_err(|e| format!("Line {}: {}", i + 1, e))?;
+        glyphs.push(Glyph { symbol, segments });
+    }
+
+    let segments = glyphs
+        .iter()
+        .map(|glyph| 32 - glyph.segments.leading_zeros() as usize)
+        .max()
+        .ok_or("There are no glyphs".to_string())?;
+    GlyphTable::new(segments, glyphs)
+}
+
+/// Parses a pattern like `cfbegad` into a bit per wire or segment, with `a`
+/// the lowest. Letters past the first `wires` and repeated letters are
+/// errors.
+pub fn parse_pattern(str: &str, wires: usize) -> Result<u32, String> {
+    let mut pattern = 0;
+    for char in str.chars() {
+        let wire = (char as u32).wrapping_sub('a' as u32);
+        if wire as usize >= wires {
+            return Err(format!(
+                "'{}' in '{}' isn't one of a to {}",
+                char,
+                str,
+                wire_name(wires - 1)
+            ));
+        }
+        if pattern & (1 << wire) != 0 {
+            return Err(format!("'{}' appears twice in '{}'", char, str));
+        }
+        pattern |= 1 << wire;
+    }
+    Ok(pattern)
+}
+
+/// The letters of the wires in `pattern`, in order.
+pub fn pattern_to_string(pattern: u32) -> String {
+    (0..32)
+        .filter(|wire| pattern & (1 << wire) != 0)
+        .map(wire_name)
+        .collect()
+}
+
+fn wire_name(wire: usize) -> char {
+    (b'a' + wire as u8) as char
+}
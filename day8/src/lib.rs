@@ -0,0 +1,9 @@
+pub mod decoder;
+pub mod encoder;
+pub mod glyphs;
+pub mod note;
+
+pub use decoder::{decode, read_output, Wiring};
+pub use encoder::encode;
+pub use glyphs::{parse_glyph_table, Glyph, GlyphTable};
+pub use note::{parse_notes, Note};
@@ -0,0 +1,42 @@
+use crate::glyphs::parse_pattern;
+
+/// One entry of the notes: the unique signal patterns seen on a display and
+/// the patterns of its output value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Note {
+    /// Line of the notes the entry is on, from 1.
+    pub line: usize,
+    pub signals: Vec<u32>,
+    pub outputs: Vec<u32>,
+}
+
+/// Parses notes of `signals | outputs` lines, with patterns over the first
+/// `wires` letters.
+pub fn parse_notes(input: &str, wires: usize) -> Result<Vec<Note>, String> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let line_number = i + 1;
+            let (signals, outputs) = line.split_once('|').ok_or(format!(
+                "Entry {}: expected signal patterns | output patterns",
+                line_number
+            ))?;
+            let parse = |patterns: &str| {
+                patterns
+                    .split_whitespace()
+                    .map(|pattern| {
+                        parse_pattern(pattern, wires)
+                            .map_err(|e| format!("Entry {}: {}", line_number, e))
+                    })
+                    .collect::<Result<Vec<u32>, String>>()
+            };
+            Ok(Note {
+                line: line_number,
+                signals: parse(signals)?,
+                outputs: parse(outputs)?,
+            })
+        })
+        .collect()
+}
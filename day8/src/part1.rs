@@ -1,43 +1,47 @@
-use std::fmt;
+use day8::{parse_notes, GlyphTable};
+use std::env;
 use std::fs;
 use std::io;
 use std::io::prelude::*;
-use std::ops::{Add, Sub};
 
+/// Counts the outputs that show 1, 4, 7 or 8, the digits with a number of
+/// segments no other digit has.
+///
+/// Usage: part1 [--glyphs 7|7-hex|14|16|FILE] [input file]
+///
+/// With other glyphs, counts the outputs showing a glyph whose number of
+/// segments is unique in the table. A glyph file has a `symbol segments`
+/// line per glyph, like `7 acf`.
 fn main() -> io::Result<()> {
-    let file = fs::File::open("./input.txt")?;
+    let mut glyphs = "7".to_string();
+    let mut path = "./input.txt".to_string();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--glyphs" => glyphs = args.next().unwrap(),
+            _ => path = arg,
+        }
+    }
+
+    let table = match GlyphTable::load(&glyphs) {
+        Ok(table) => table,
+        Err(e) => panic!("{}", e),
+    };
+    let file = fs::File::open(path)?;
     let mut reader = io::BufReader::new(file);
     let mut input = String::new();
     reader.read_to_string(&mut input)?;
 
-    let inputs: Vec<(Vec<&str>, Vec<&str>)> = input
-        .lines()
-        .map(|line| line.split_once("|").unwrap())
-        .map(|(signals, output)| {
-            let parsed: (Vec<&str>, Vec<&str>) = (
-                signals.trim().split(" ").collect(),
-                output.trim().split(" ").collect(),
-            );
-            return parsed;
-        })
-        .collect();
-
-    let output_lengths: Vec<usize> = inputs
-        .iter()
-        .map(|(_signals, outputs)| outputs)
-        .flat_map(|outputs| outputs.iter().map(|output| output.len()))
-        .collect();
+    let notes = match parse_notes(&input, table.segments()) {
+        Ok(notes) => notes,
+        Err(e) => panic!("{}", e),
+    };
 
-    let outputs_with_2_3_4_or_7_digits: Vec<usize> = output_lengths
+    let how_many = notes
         .iter()
-        .cloned()
-        .filter(|length| {
-            let length = *length as i32;
-            length == 2 || length == 3 || length == 4 || length == 7
-        })
-        .collect();
-
-    let how_many = outputs_with_2_3_4_or_7_digits.len();
+        .flat_map(|note| note.outputs.iter())
+        .filter(|output| table.of_size(output.count_ones()).len() == 1)
+        .count();
 
     println!("{} outputs with unique segments", how_many);
 